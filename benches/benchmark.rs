@@ -1,5 +1,35 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
 use the_zipper::*;
+
+/// `MemoLocation::get_nth` without the negative cache: only found children are cached.
+struct PositiveMemo<T: Clone> {
+    location: Location<T>,
+    cache: RefCell<HashMap<usize, Rc<Location<T>>>>,
+}
+
+impl<T: Clone> PositiveMemo<T> {
+    fn new(location: Location<T>) -> Self {
+        Self {
+            location,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn get_nth(&self, n: usize) -> Option<Rc<Location<T>>> {
+        if let Some(child) = self.cache.borrow().get(&n) {
+            return Some(child.clone());
+        }
+
+        let child = Rc::new(self.location.clone().get_nth(n)?);
+        self.cache.borrow_mut().insert(n, child.clone());
+        Some(child)
+    }
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("go up", |b| {
         b.iter(|| {
@@ -67,6 +97,45 @@ pub fn criterion_benchmark(c: &mut Criterion) {
             location.go_left().map(Location::go_right)
         })
     });
+
+    let wide = Location {
+        path: Path::Top.into(),
+        cursor: Tree::Section((0..1_000).map(Tree::Item).collect()),
+    }
+    .get_nth(500)
+    .unwrap();
+
+    c.bench_function("go to root", |b| {
+        b.iter(|| black_box(wide.clone()).go_to_root())
+    });
+    c.bench_function("to tree prealloc", |b| {
+        b.iter(|| black_box(wide.clone()).to_tree_prealloc())
+    });
+
+    let parent = wide.go_up().unwrap();
+    let memo = MemoLocation::new(parent.clone());
+    let positive_memo = PositiveMemo::new(parent);
+    memo.get_nth(5_000);
+    positive_memo.get_nth(5_000);
+
+    c.bench_function("memo get nth out of bounds without negative cache", |b| {
+        b.iter(|| black_box(&positive_memo).get_nth(black_box(5_000)))
+    });
+    c.bench_function("memo get nth out of bounds with negative cache", |b| {
+        b.iter(|| black_box(&memo).get_nth(black_box(5_000)))
+    });
+
+    let deep = (0..10).fold(Tree::Item(0), |tree, _| {
+        Tree::Section([tree].into_iter().chain((1..10).map(Tree::Item)).collect())
+    });
+
+    c.bench_function("go to root from 10 levels deep", |b| {
+        b.iter_batched(
+            || Location::rooted(deep.clone()).down_first_n(10).unwrap(),
+            |location| location.go_to_root(),
+            BatchSize::SmallInput,
+        )
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,390 @@
+//! Recording edits as index-addressed operations and replaying them.
+//!
+//! A [`Recorder`] wraps a [`crate::Location`] and logs every edit into an
+//! [`EditLog`], which can later be applied to another copy of the same tree.
+
+use std::fmt::{self, Display};
+
+use crate::{Location, Path, Tree};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents a single edit, addressed by the index path of the node it applies to.
+///
+/// Index paths are taken from the root of the tree as it was right before
+/// the edit, so a sequence of edits can be applied deterministically.
+pub enum Edit<T: Clone> {
+    /// Replaces the node at `at` with `tree`.
+    Change {
+        /// Index path of the edited node.
+        at: Vec<usize>,
+        /// The replacement tree.
+        tree: Tree<T>,
+    },
+    /// Inserts `tree` to the left of the node at `at`.
+    InsertLeft {
+        /// Index path of the edited node.
+        at: Vec<usize>,
+        /// The inserted tree.
+        tree: Tree<T>,
+    },
+    /// Inserts `tree` to the right of the node at `at`.
+    InsertRight {
+        /// Index path of the edited node.
+        at: Vec<usize>,
+        /// The inserted tree.
+        tree: Tree<T>,
+    },
+    /// Inserts `tree` as the first child of the section at `at`.
+    InsertDown {
+        /// Index path of the edited node.
+        at: Vec<usize>,
+        /// The inserted tree.
+        tree: Tree<T>,
+    },
+    /// Deletes the node at `at`.
+    Delete {
+        /// Index path of the edited node.
+        at: Vec<usize>,
+    },
+}
+
+impl<T: Clone> Edit<T> {
+    /// Returns the index path of the node the edit applies to.
+    pub fn at(&self) -> &[usize] {
+        match self {
+            Edit::Change { at, .. }
+            | Edit::InsertLeft { at, .. }
+            | Edit::InsertRight { at, .. }
+            | Edit::InsertDown { at, .. }
+            | Edit::Delete { at } => at,
+        }
+    }
+
+    fn apply(&self, location: Location<T>) -> Option<Location<T>> {
+        match self {
+            Edit::Change { tree, .. } => Some(location.change(tree.clone())),
+            Edit::InsertLeft { tree, .. } => location.insert_left(tree.clone()),
+            Edit::InsertRight { tree, .. } => location.insert_right(tree.clone()),
+            Edit::InsertDown { tree, .. } => location.insert_down(tree.clone()),
+            Edit::Delete { .. } => location.delete(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An ordered list of edits that can be replayed onto another copy of a tree.
+pub struct EditLog<T: Clone> {
+    /// The recorded edits, oldest first.
+    pub edits: Vec<Edit<T>>,
+}
+
+impl<T: Clone> EditLog<T> {
+    /// Creates an empty edit log.
+    pub fn new() -> Self {
+        Self { edits: vec![] }
+    }
+}
+
+impl<T: Clone> Default for EditLog<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The error returned when an edit log cannot be replayed onto a tree.
+pub enum ReplayError {
+    /// The index path of the edit at position `edit` does not exist in the tree.
+    InvalidPath {
+        /// Position of the failing edit in the log.
+        edit: usize,
+    },
+    /// The edit at position `edit` cannot be applied to the node it addresses.
+    Rejected {
+        /// Position of the failing edit in the log.
+        edit: usize,
+    },
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::InvalidPath { edit } => {
+                write!(f, "edit {edit} addresses a node that does not exist")
+            }
+            ReplayError::Rejected { edit } => write!(f, "edit {edit} cannot be applied"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl<T: Clone> Tree<T> {
+    /// Applies every edit of a log to the tree, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `log` - The edits to apply.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Tree)` - The edited tree.
+    /// * `Err(ReplayError)` - Describing the first edit that could not be applied.
+    pub fn replay(self, log: &EditLog<T>) -> Result<Tree<T>, ReplayError> {
+        log.edits
+            .iter()
+            .enumerate()
+            .try_fold(self, |tree, (index, edit)| {
+                let location = Location::from_root_and_indices(tree, edit.at())
+                    .ok_or(ReplayError::InvalidPath { edit: index })?;
+
+                edit.apply(location)
+                    .map(|location| location.rekey().0)
+                    .ok_or(ReplayError::Rejected { edit: index })
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A location that records every edit made through it into an `EditLog`.
+///
+/// Navigation is not recorded: every edit stores the index path of the node
+/// it was made at instead.
+pub struct Recorder<T: Clone> {
+    location: Location<T>,
+    log: EditLog<T>,
+}
+
+impl<T: Clone> Recorder<T> {
+    /// Creates a recorder focused on the root of a tree, at `Path::Top`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to edit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Recorder` with an empty log.
+    pub fn new(tree: Tree<T>) -> Self {
+        Self {
+            location: Location {
+                cursor: tree,
+                path: Path::Top.into(),
+            },
+            log: EditLog::new(),
+        }
+    }
+
+    /// Returns the current location.
+    pub fn location(&self) -> &Location<T> {
+        &self.location
+    }
+
+    /// Returns the edits recorded so far.
+    pub fn log(&self) -> &EditLog<T> {
+        &self.log
+    }
+
+    /// Finishes recording.
+    ///
+    /// # Returns
+    ///
+    /// The root of the edited tree and the recorded log.
+    pub fn finish(self) -> (Tree<T>, EditLog<T>) {
+        (self.location.rekey().0, self.log)
+    }
+
+    /// Moves the cursor to the left sibling, see [`Location::go_left`].
+    pub fn go_left(self) -> Option<Self> {
+        self.navigate(Location::go_left)
+    }
+
+    /// Moves the cursor to the right sibling, see [`Location::go_right`].
+    pub fn go_right(self) -> Option<Self> {
+        self.navigate(Location::go_right)
+    }
+
+    /// Moves the cursor to the parent node, see [`Location::go_up`].
+    pub fn go_up(self) -> Option<Self> {
+        self.navigate(Location::go_up)
+    }
+
+    /// Moves the cursor to the first child node, see [`Location::go_down`].
+    pub fn go_down(self) -> Option<Self> {
+        self.navigate(Location::go_down)
+    }
+
+    /// Replaces the current node and records the edit, see [`Location::change`].
+    pub fn change(self, tree: Tree<T>) -> Self {
+        let at = self.location.path_indices();
+        self.record(Edit::Change { at, tree })
+            .expect("a change can always be applied")
+    }
+
+    /// Inserts a tree to the left and records the edit, see [`Location::insert_left`].
+    pub fn insert_left(self, tree: Tree<T>) -> Option<Self> {
+        let at = self.location.path_indices();
+        self.record(Edit::InsertLeft { at, tree })
+    }
+
+    /// Inserts a tree to the right and records the edit, see [`Location::insert_right`].
+    pub fn insert_right(self, tree: Tree<T>) -> Option<Self> {
+        let at = self.location.path_indices();
+        self.record(Edit::InsertRight { at, tree })
+    }
+
+    /// Inserts a first child and records the edit, see [`Location::insert_down`].
+    pub fn insert_down(self, tree: Tree<T>) -> Option<Self> {
+        let at = self.location.path_indices();
+        self.record(Edit::InsertDown { at, tree })
+    }
+
+    /// Deletes the current node and records the edit, see [`Location::delete`].
+    pub fn delete(self) -> Option<Self> {
+        let at = self.location.path_indices();
+        self.record(Edit::Delete { at })
+    }
+
+    fn navigate(self, step: fn(Location<T>) -> Option<Location<T>>) -> Option<Self> {
+        Some(Self {
+            location: step(self.location)?,
+            log: self.log,
+        })
+    }
+
+    fn record(mut self, edit: Edit<T>) -> Option<Self> {
+        let location = edit.apply(self.location)?;
+        self.log.edits.push(edit);
+
+        Some(Self {
+            location,
+            log: self.log,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{Edit, EditLog, Recorder, ReplayError, Tree};
+
+    #[test]
+    fn test_record() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let recorder = Recorder::new(tree)
+            .go_down()
+            .and_then(Recorder::go_right)
+            .map(|recorder| recorder.change(Tree::Item("-")))
+            .and_then(|recorder| recorder.insert_left(Tree::Item("(")))
+            .unwrap();
+
+        assert_eq!(recorder.location().cursor, Tree::Item("-"));
+        assert_eq!(
+            recorder.log(),
+            &EditLog {
+                edits: vec![
+                    Edit::Change {
+                        at: vec![1],
+                        tree: Tree::Item("-"),
+                    },
+                    Edit::InsertLeft {
+                        at: vec![1],
+                        tree: Tree::Item("("),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let (edited, log) = Recorder::new(tree.clone())
+            .go_down()
+            .and_then(|recorder| recorder.insert_right(Tree::Item("+")))
+            .and_then(Recorder::go_right)
+            .and_then(Recorder::go_right)
+            .and_then(|recorder| recorder.insert_down(Tree::Item("x")))
+            .and_then(Recorder::go_right)
+            .and_then(Recorder::delete)
+            .and_then(Recorder::go_up)
+            .and_then(Recorder::go_left)
+            .map(|recorder| recorder.change(Tree::Item("-")))
+            .unwrap()
+            .finish();
+
+        assert_eq!(
+            edited,
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Item("-"),
+                Tree::Section(vec![Tree::Item("x"), Tree::Item("c")]),
+            ])
+        );
+        assert_eq!(log.edits.len(), 4);
+        assert_eq!(tree.replay(&log), Ok(edited));
+    }
+
+    #[test]
+    fn test_replay_error() {
+        let log = EditLog {
+            edits: vec![
+                Edit::Delete { at: vec![0] },
+                Edit::InsertDown {
+                    at: vec![0],
+                    tree: Tree::Item("x"),
+                },
+            ],
+        };
+
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+        let error = tree.replay(&log).unwrap_err();
+        assert_eq!(error, ReplayError::Rejected { edit: 1 });
+        assert_eq!(error.to_string(), "edit 1 cannot be applied");
+
+        let error = Tree::Item("a").replay(&log).unwrap_err();
+        assert_eq!(error, ReplayError::InvalidPath { edit: 0 });
+        assert_eq!(
+            error.to_string(),
+            "edit 0 addresses a node that does not exist"
+        );
+    }
+
+    #[test]
+    fn test_replay_empty_log() {
+        let tree = Tree::Section(vec![Tree::Item("a")]);
+
+        assert_eq!(tree.clone().replay(&EditLog::default()), Ok(tree));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_and_replay() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a".to_string()),
+            Tree::Item("b".to_string()),
+        ]);
+
+        let (edited, log) = Recorder::new(tree.clone())
+            .go_down()
+            .and_then(|recorder| recorder.insert_right(Tree::Item("+".to_string())))
+            .and_then(Recorder::go_right)
+            .and_then(Recorder::go_right)
+            .map(|recorder| recorder.change(Tree::Item("c".to_string())))
+            .unwrap()
+            .finish();
+
+        let json = serde_json::to_string(&log).unwrap();
+        let restored: EditLog<String> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, log);
+        assert_eq!(tree.replay(&restored), Ok(edited));
+    }
+}
@@ -0,0 +1,246 @@
+//! Undo and redo for edits made at a location, see [`History`].
+
+use std::collections::VecDeque;
+
+use crate::{Location, Tree};
+
+#[derive(Debug, Clone, PartialEq)]
+/// A location that remembers its past states for undo and redo.
+///
+/// Every successful edit made through the history (`change`, `insert_*`,
+/// `delete`) keeps a snapshot of the location before the edit. Snapshots
+/// share their `Rc` paths with each other, so they are cheap to keep.
+/// Navigation is not recorded, but undoing an edit also restores the cursor
+/// position it was made at.
+pub struct History<T: Clone> {
+    location: Location<T>,
+    undo: VecDeque<Location<T>>,
+    redo: Vec<Location<T>>,
+    capacity: Option<usize>,
+}
+
+impl<T: Clone> History<T> {
+    /// Creates a history with no limit on the number of undo steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - The location to edit.
+    ///
+    /// # Returns
+    ///
+    /// A new `History` with nothing to undo.
+    pub fn new(location: Location<T>) -> Self {
+        Self {
+            location,
+            undo: VecDeque::new(),
+            redo: vec![],
+            capacity: None,
+        }
+    }
+
+    /// Creates a history keeping at most `capacity` undo steps.
+    ///
+    /// When an edit would exceed the capacity, the oldest step is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - The location to edit.
+    /// * `capacity` - The maximum number of undo steps.
+    ///
+    /// # Returns
+    ///
+    /// A new `History` with nothing to undo.
+    pub fn with_capacity(location: Location<T>, capacity: usize) -> Self {
+        Self {
+            location,
+            undo: VecDeque::with_capacity(capacity),
+            redo: vec![],
+            capacity: Some(capacity),
+        }
+    }
+
+    /// Returns the current location.
+    pub fn location(&self) -> &Location<T> {
+        &self.location
+    }
+
+    /// Unwraps the current location, dropping the history.
+    pub fn into_inner(self) -> Location<T> {
+        self.location
+    }
+
+    /// Moves the cursor to the left sibling without recording it, see [`Location::go_left`].
+    pub fn go_left(&mut self) -> Option<&Location<T>> {
+        self.navigate(Location::go_left)
+    }
+
+    /// Moves the cursor to the right sibling without recording it, see [`Location::go_right`].
+    pub fn go_right(&mut self) -> Option<&Location<T>> {
+        self.navigate(Location::go_right)
+    }
+
+    /// Moves the cursor to the parent node without recording it, see [`Location::go_up`].
+    pub fn go_up(&mut self) -> Option<&Location<T>> {
+        self.navigate(Location::go_up)
+    }
+
+    /// Moves the cursor to the first child node without recording it, see [`Location::go_down`].
+    pub fn go_down(&mut self) -> Option<&Location<T>> {
+        self.navigate(Location::go_down)
+    }
+
+    /// Replaces the current node and records the edit, see [`Location::change`].
+    pub fn change(&mut self, tree: Tree<T>) -> &Location<T> {
+        self.record(|location| Some(location.change(tree)))
+            .expect("a change can always be applied")
+    }
+
+    /// Inserts a tree to the left and records the edit, see [`Location::insert_left`].
+    pub fn insert_left(&mut self, tree: Tree<T>) -> Option<&Location<T>> {
+        self.record(|location| location.insert_left(tree))
+    }
+
+    /// Inserts a tree to the right and records the edit, see [`Location::insert_right`].
+    pub fn insert_right(&mut self, tree: Tree<T>) -> Option<&Location<T>> {
+        self.record(|location| location.insert_right(tree))
+    }
+
+    /// Inserts a first child and records the edit, see [`Location::insert_down`].
+    pub fn insert_down(&mut self, tree: Tree<T>) -> Option<&Location<T>> {
+        self.record(|location| location.insert_down(tree))
+    }
+
+    /// Deletes the current node and records the edit, see [`Location::delete`].
+    pub fn delete(&mut self) -> Option<&Location<T>> {
+        self.record(Location::delete)
+    }
+
+    /// Reverts the most recent edit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Location)` - The location as it was before the edit.
+    /// * `None` - If there is nothing to undo.
+    pub fn undo(&mut self) -> Option<&Location<T>> {
+        let previous = self.undo.pop_back()?;
+        self.redo
+            .push(std::mem::replace(&mut self.location, previous));
+        Some(&self.location)
+    }
+
+    /// Reapplies the most recently undone edit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Location)` - The location as it was after the edit.
+    /// * `None` - If there is nothing to redo, or a new edit was made since the last undo.
+    pub fn redo(&mut self) -> Option<&Location<T>> {
+        let next = self.redo.pop()?;
+        let previous = std::mem::replace(&mut self.location, next);
+        self.push_undo(previous);
+        Some(&self.location)
+    }
+
+    fn navigate(&mut self, step: fn(Location<T>) -> Option<Location<T>>) -> Option<&Location<T>> {
+        self.location = step(self.location.clone())?;
+        Some(&self.location)
+    }
+
+    fn record<F: FnOnce(Location<T>) -> Option<Location<T>>>(
+        &mut self,
+        edit: F,
+    ) -> Option<&Location<T>> {
+        let edited = edit(self.location.clone())?;
+        let previous = std::mem::replace(&mut self.location, edited);
+
+        self.push_undo(previous);
+        self.redo.clear();
+        Some(&self.location)
+    }
+
+    fn push_undo(&mut self, location: Location<T>) {
+        if self.capacity == Some(0) {
+            return;
+        }
+        if Some(self.undo.len()) == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(location);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{History, Location, Tree};
+
+    #[test]
+    fn test_undo_delete() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+        let mut history = History::new(Location::rooted(tree.clone()));
+
+        history.go_down().unwrap();
+        let before = history.location().clone();
+
+        assert_eq!(history.delete().unwrap().cursor, Tree::Item("b"));
+        assert_eq!(history.undo(), Some(&before));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.into_inner().go_to_root(), tree);
+    }
+
+    #[test]
+    fn test_redo_after_undo() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+        let mut history = History::new(Location::rooted(tree));
+
+        history.go_down().unwrap();
+        history.change(Tree::Item("x"));
+        let inserted = history.insert_right(Tree::Item("y")).unwrap().clone();
+
+        history.undo().unwrap();
+        assert_eq!(history.location().cursor, Tree::Item("x"));
+        history.undo().unwrap();
+        assert_eq!(history.location().cursor, Tree::Item("a"));
+
+        history.redo().unwrap();
+        assert_eq!(history.redo(), Some(&inserted));
+        assert_eq!(history.redo(), None);
+        assert_eq!(
+            history.location().clone().go_to_root(),
+            Tree::Section(vec![Tree::Item("x"), Tree::Item("y"), Tree::Item("b")])
+        );
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo() {
+        let mut history = History::new(Location::rooted(Tree::Section(vec![Tree::Item("a")])));
+
+        history.go_down().unwrap();
+        history.change(Tree::Item("b"));
+        history.undo().unwrap();
+        history.change(Tree::Item("c"));
+
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.location().cursor, Tree::Item("c"));
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut history =
+            History::with_capacity(Location::rooted(Tree::Section(vec![Tree::Item(0)])), 2);
+
+        history.go_down().unwrap();
+        for value in 1..=3 {
+            history.change(Tree::Item(value));
+        }
+        assert!(history.insert_down(Tree::Item(9)).is_none());
+
+        assert_eq!(history.undo().unwrap().cursor, Tree::Item(2));
+        assert_eq!(history.undo().unwrap().cursor, Tree::Item(1));
+        assert_eq!(history.undo(), None);
+
+        let mut history = History::with_capacity(Location::rooted(Tree::Item(0)), 0);
+        history.change(Tree::Item(1));
+        assert_eq!(history.undo(), None);
+    }
+}
@@ -76,12 +76,40 @@
 //!
 //! This project is licensed under the MIT License. See the [LICENSE](LICENSE) file for details.
 
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{self, Display, Write};
+use std::hash::Hash;
 use std::rc::Rc;
 
-#[derive(Debug, Clone, PartialEq)]
+mod edit_log;
+mod history;
+mod list;
+mod memo;
+mod meta;
+mod pattern;
+mod shared;
+mod sync;
+pub mod zippable;
+
+pub use edit_log::{Edit, EditLog, Recorder, ReplayError};
+pub use history::History;
+pub use list::ListLocation;
+pub use memo::MemoLocation;
+pub use meta::{MetaLocation, MetaPath, MetaTree};
+pub use pattern::Pattern;
+pub use shared::SharedTree;
+pub use sync::{SyncLocation, SyncPath};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a hierarchical tree structure.
 ///
 /// A tree can either be a single item or a section containing multiple trees.
+///
+/// Trees of ordered values are ordered as follows, which is also the order
+/// of a `BTreeSet<Tree<T>>`: every item comes before every section, items are
+/// compared by their values, and sections compare their children
+/// lexicographically, so a section that is a prefix of another comes first.
 pub enum Tree<T: Clone> {
     /// A single item value of type T.
     Item(T),
@@ -89,886 +117,6840 @@ pub enum Tree<T: Clone> {
     Section(Vec<Tree<T>>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-/// Represents a path within a tree, used for navigation and context tracking.
-///
-/// The path keeps track of the location in the tree structure,
-/// remembering the siblings to the left and right of the current position,
-/// as well as the path to the parent.
-pub enum Path<T: Clone> {
-    /// Represents the top level of the tree hierarchy.
-    Top,
-    /// Represents a position within the tree structure.
-    Node {
-        /// Trees to the left of the current position.
-        left: Vec<Tree<T>>,
-        /// Trees to the right of the current position.
-        right: Vec<Tree<T>>,
-        /// Path to the parent node.
-        path: Rc<Path<T>>,
-    },
-}
+impl<T: Clone> Tree<T> {
+    /// The maximum number of replacements made by a single call to [`Tree::rewrite`].
+    pub const REWRITE_LIMIT: usize = 10_000;
 
-#[derive(Debug, PartialEq, Clone)]
-/// Represents a location (cursor) within a tree.
-///
-/// A location combines a cursor pointing to the current tree node
-/// and a path providing context for navigation within the overall tree structure.
-pub struct Location<T: Clone> {
-    /// The current tree node being focused on.
-    pub cursor: Tree<T>,
-    /// The path representing the context of this location within the overall tree.
-    pub path: Rc<Path<T>>,
-}
+    /// Creates an item holding a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the item.
+    ///
+    /// # Returns
+    ///
+    /// `Tree::Item(value)`.
+    pub fn item(value: T) -> Tree<T> {
+        Tree::Item(value)
+    }
 
-impl<T: Clone> Location<T> {
-    /// Creates a new location from a tree.
+    /// Creates a section from its children.
     ///
     /// # Arguments
     ///
-    /// * `tree` - The tree to create a location from.
+    /// * `children` - The children of the section, in order.
     ///
     /// # Returns
     ///
-    /// A new `Location` instance with the given tree as cursor.
-    pub fn new(tree: Tree<T>) -> Self {
-        Self {
-            cursor: tree.clone(),
-            path: Path::Node {
-                left: vec![],
-                right: vec![tree.clone()],
-                path: Rc::new(Path::Top),
-            }
-            .into(),
-        }
+    /// A `Tree::Section` holding the children.
+    pub fn section(children: impl IntoIterator<Item = Tree<T>>) -> Tree<T> {
+        Tree::Section(children.into_iter().collect())
     }
 
-    /// Moves the cursor to the left sibling.
+    /// Pads every section to exactly `width` children.
+    ///
+    /// Missing children are appended as `Tree::Item(fill)`, recursively through
+    /// the whole tree. Sections that already have `width` or more children are
+    /// left at their original length (they are never truncated), although
+    /// their children are still padded.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of children every section should have.
+    /// * `fill` - The value used for the padding items.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If there is a left sibling.
-    /// * `None` - If there is no left sibling or the location is at the top.
-    pub fn go_left(self) -> Option<Self> {
-        match self.path.as_ref() {
-            Path::Top => None,
-            Path::Node { left, right, path } => left.split_first().map(|(first, rest)| Self {
-                cursor: first.clone(),
-                path: Path::Node {
-                    left: rest.to_vec(),
-                    path: path.clone(),
-                    right: vec![self.cursor].into_iter().chain(right.clone()).collect(),
+    /// A new tree with every section padded to `width` children.
+    pub fn pad_sections_to(&self, width: usize, fill: T) -> Tree<T> {
+        match self {
+            Tree::Item(value) => Tree::Item(value.clone()),
+            Tree::Section(children) => {
+                let mut children = children
+                    .iter()
+                    .map(|child| child.pad_sections_to(width, fill.clone()))
+                    .collect::<Vec<Tree<T>>>();
+
+                if children.len() < width {
+                    children.resize(width, Tree::Item(fill));
                 }
-                .into(),
-            }),
+
+                Tree::Section(children)
+            }
         }
     }
 
-    /// Moves the cursor to the right sibling.
+    /// Folds the tree bottom-up.
+    ///
+    /// Like [`Tree::fold_with_path`], without the index paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `leaf` - Produces a result for an item from its value.
+    /// * `section` - Combines the results of a section's children.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If there is a right sibling.
-    /// * `None` - If there is no right sibling or the location is at the top.
-    pub fn go_right(self) -> Option<Self> {
-        match self.path.as_ref() {
-            Path::Top => None,
-            Path::Node { left, right, path } => right.split_first().map(|(first, rest)| Self {
-                cursor: first.clone(),
-                path: Path::Node {
-                    left: vec![self.cursor].into_iter().chain(left.clone()).collect(),
-                    right: rest.to_vec(),
-                    path: path.clone(),
-                }
-                .into(),
-            }),
-        }
+    /// The result produced for the root of the tree.
+    pub fn fold<B, L, S>(&self, leaf: L, section: S) -> B
+    where
+        L: Fn(&T) -> B,
+        S: Fn(Vec<B>) -> B,
+    {
+        self.fold_with_path(|_, value| leaf(value), |_, results| section(results))
     }
 
-    /// Moves the cursor to the parent node.
+    /// Folds the tree bottom-up, passing each node's index path to the combining functions.
+    ///
+    /// The index path of a node lists the child index taken at every level
+    /// from this tree down to that node; the tree itself has an empty path.
+    ///
+    /// # Arguments
+    ///
+    /// * `leaf` - Produces a result for an item from its path and value.
+    /// * `section` - Combines the results of a section's children, given the section's path.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If there is a parent node.
-    /// * `None` - If the location is at the top.
-    pub fn go_up(self) -> Option<Self> {
-        match self.path.as_ref() {
-            Path::Top => None,
-            Path::Node { left, right, path } => {
-                let left = left.iter().rev().cloned().collect::<Vec<Tree<T>>>();
-                Self {
-                    path: path.clone(),
-                    cursor: Tree::Section(
-                        [left, vec![self.cursor], right.clone()]
-                            .iter()
-                            .flatten()
-                            .cloned()
-                            .collect::<Vec<Tree<T>>>(),
-                    ),
-                }
-                .into()
+    /// The result produced for the root of the tree.
+    pub fn fold_with_path<B, L, S>(&self, leaf: L, section: S) -> B
+    where
+        L: Fn(&[usize], &T) -> B,
+        S: Fn(&[usize], Vec<B>) -> B,
+    {
+        self.fold_with_path_at(&mut vec![], &leaf, &section)
+    }
+
+    fn fold_with_path_at<B, L, S>(&self, path: &mut Vec<usize>, leaf: &L, section: &S) -> B
+    where
+        L: Fn(&[usize], &T) -> B,
+        S: Fn(&[usize], Vec<B>) -> B,
+    {
+        match self {
+            Tree::Item(value) => leaf(path, value),
+            Tree::Section(children) => {
+                let results = children
+                    .iter()
+                    .enumerate()
+                    .map(|(index, child)| {
+                        path.push(index);
+                        let result = child.fold_with_path_at(path, leaf, section);
+                        path.pop();
+                        result
+                    })
+                    .collect::<Vec<B>>();
+
+                section(path, results)
             }
         }
     }
 
-    /// Moves the cursor to the first child node.
+    /// Returns the node at the given index path, without building a `Location`.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - Child indices leading from this node to the target node.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If the current node is a section with at least one child.
-    /// * `None` - If the current node is an item or an empty section.
-    pub fn go_down(self) -> Option<Self> {
-        match self.cursor {
+    /// * `Some(&Tree)` - If the index path exists.
+    /// * `None` - If an index is out of bounds or the path runs into an item.
+    pub fn get(&self, indices: &[usize]) -> Option<&Tree<T>> {
+        indices.iter().try_fold(self, |tree, &index| match tree {
             Tree::Item(_) => None,
-            Tree::Section(trees) => trees.split_first().map(|(first, rest)| Self {
-                cursor: first.clone(),
-                path: Path::Node {
-                    left: vec![],
-                    right: rest.into(),
-                    path: self.path,
-                }
-                .into(),
-            }),
-        }
+            Tree::Section(children) => children.get(index),
+        })
     }
 
-    /// Gets the nth child of the current node.
-    ///
-    /// This is equivalent to n calls to `go_right()`.
+    /// Returns the node at the given index path for editing, see [`Tree::get`].
     ///
     /// # Arguments
     ///
-    /// * `n` - The index of the child to navigate to.
+    /// * `indices` - Child indices leading from this node to the target node.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If the child exists.
-    /// * `None` - If the child doesn't exist or the current node is an item.
-    pub fn get_nth(self, n: usize) -> Option<Self> {
-        match n {
-            0 => self.go_down(),
-            n => self.get_nth(n - 1).and_then(Location::go_right),
+    /// * `Some(&mut Tree)` - If the index path exists.
+    /// * `None` - If an index is out of bounds or the path runs into an item.
+    pub fn get_mut(&mut self, indices: &[usize]) -> Option<&mut Tree<T>> {
+        match indices.split_first() {
+            None => Some(self),
+            Some((first, rest)) => match self {
+                Tree::Item(_) => None,
+                Tree::Section(children) => children.get_mut(*first)?.get_mut(rest),
+            },
         }
     }
 
-    /// Replaces the current node with a new tree.
+    /// Checks whether this tree appears as a subtree anywhere within `other`.
+    ///
+    /// A tree is a subtree of itself.
     ///
     /// # Arguments
     ///
-    /// * `tree` - The new tree to replace the current node with.
+    /// * `other` - The tree to search in.
     ///
     /// # Returns
     ///
-    /// A new location with the updated cursor.
-    pub fn change(self, tree: Tree<T>) -> Self {
-        Self {
-            cursor: tree,
-            path: self.path,
+    /// `true` if `other` or one of its descendants is equal to this tree.
+    pub fn is_subtree_of(&self, other: &Tree<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        self == other
+            || match other {
+                Tree::Item(_) => false,
+                Tree::Section(children) => children.iter().any(|child| self.is_subtree_of(child)),
+            }
+    }
+
+    /// Counts the unique leaf values in the tree.
+    ///
+    /// # Returns
+    ///
+    /// The number of distinct values held by the items of the tree.
+    pub fn distinct_leaf_count(&self) -> usize
+    where
+        T: Eq + Hash,
+    {
+        self.items().collect::<HashSet<&T>>().len()
+    }
+
+    /// Shrinks the capacity of every section's child vector as much as possible.
+    ///
+    /// Edits can leave the vectors of long-lived trees with excess capacity;
+    /// this reclaims it recursively through the whole tree.
+    pub fn shrink_to_fit(&mut self) {
+        if let Tree::Section(children) = self {
+            children.shrink_to_fit();
+            children.iter_mut().for_each(Tree::shrink_to_fit);
         }
     }
 
-    /// Inserts a new tree to the right of the current node.
+    /// Groups the children of a section into sub-sections separated by `is_sep`.
+    ///
+    /// Separators are dropped and every run of children between them becomes
+    /// a section of its own, following the rules of [`slice::split`]: a leading
+    /// or trailing separator, or two consecutive separators, produce an empty
+    /// group, and a section without separators becomes a single group. Only
+    /// the direct children are inspected. An item is returned unchanged.
     ///
     /// # Arguments
     ///
-    /// * `tree` - The tree to insert.
+    /// * `is_sep` - Returns `true` for children that separate the groups.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If the insertion was successful.
-    /// * `None` - If the location is at the top.
-    pub fn insert_right(self, tree: Tree<T>) -> Option<Self> {
-        match self.path.as_ref() {
-            Path::Top => None,
-            Path::Node { left, right, path } => Self {
-                cursor: self.cursor.clone(),
-                path: Path::Node {
-                    left: left.clone(),
-                    path: path.clone(),
-                    right: vec![tree].into_iter().chain(right.clone()).collect(),
-                }
-                .into(),
-            }
-            .into(),
+    /// A section of sections holding the groups, or the item itself.
+    pub fn split_children<F: Fn(&Tree<T>) -> bool>(&self, is_sep: F) -> Tree<T> {
+        match self {
+            Tree::Item(value) => Tree::Item(value.clone()),
+            Tree::Section(children) => Tree::Section(
+                children
+                    .split(is_sep)
+                    .map(|group| Tree::Section(group.to_vec()))
+                    .collect(),
+            ),
         }
     }
 
-    /// Inserts a new tree to the left of the current node.
+    fn update_from_at<F>(self, other: Option<&Tree<T>>, f: &F) -> Tree<T>
+    where
+        F: Fn(&Tree<T>, Option<&Tree<T>>) -> Tree<T>,
+    {
+        let tree = match self {
+            Tree::Item(value) => Tree::Item(value),
+            Tree::Section(children) => {
+                let others = match other {
+                    Some(Tree::Section(others)) => others.as_slice(),
+                    _ => &[],
+                };
+
+                Tree::Section(
+                    children
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, child)| child.update_from_at(others.get(index), f))
+                        .collect(),
+                )
+            }
+        };
+
+        f(&tree, other)
+    }
+
+    /// Collects the ancestors of the node at an index path.
     ///
     /// # Arguments
     ///
-    /// * `tree` - The tree to insert.
+    /// * `indices` - The child index to take at every level, outermost first.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If the insertion was successful.
-    /// * `None` - If the location is at the top.
-    pub fn insert_left(self, tree: Tree<T>) -> Option<Self> {
-        match self.path.as_ref() {
-            Path::Top => None,
-            Path::Node { left, right, path } => Self {
-                cursor: self.cursor.clone(),
-                path: Path::Node {
-                    left: vec![tree].into_iter().chain(left.clone()).collect(),
-                    right: right.to_vec(),
-                    path: path.clone(),
+    /// References to every node from this tree down to, but not including,
+    /// the target node. The vector is empty when the path is empty or does
+    /// not exist in the tree.
+    pub fn ancestors_of(&self, indices: &[usize]) -> Vec<&Tree<T>> {
+        let mut ancestors = vec![];
+        let mut tree = self;
+
+        for index in indices {
+            match tree {
+                Tree::Section(children) if *index < children.len() => {
+                    ancestors.push(tree);
+                    tree = &children[*index];
                 }
-                .into(),
+                _ => return vec![],
             }
-            .into(),
         }
+
+        ancestors
     }
 
-    /// Inserts a new tree as the first child of the current node.
+    /// Maps the items of the tree while threading a state through them in document order.
     ///
     /// # Arguments
     ///
-    /// * `tree` - The tree to insert.
+    /// * `init` - The initial state.
+    /// * `f` - Takes the current state and an item value, and returns the next
+    ///   state together with the new value.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If the current node is a section.
-    /// * `None` - If the current node is an item.
-    pub fn insert_down(self, tree: Tree<T>) -> Option<Self> {
-        match self.cursor {
-            Tree::Item(_) => None,
-            Tree::Section(children) => Some(Self {
-                cursor: tree,
-                path: Path::Node {
-                    left: vec![],
-                    right: children,
-                    path: self.path,
+    /// The final state and a tree of the same shape holding the new values.
+    pub fn map_accum<S, U: Clone, F: FnMut(S, &T) -> (S, U)>(
+        &self,
+        init: S,
+        mut f: F,
+    ) -> (S, Tree<U>) {
+        self.map_accum_at(init, &mut f)
+    }
+
+    fn map_accum_at<S, U: Clone, F: FnMut(S, &T) -> (S, U)>(
+        &self,
+        state: S,
+        f: &mut F,
+    ) -> (S, Tree<U>) {
+        match self {
+            Tree::Item(value) => {
+                let (state, value) = f(state, value);
+                (state, Tree::Item(value))
+            }
+            Tree::Section(children) => {
+                let mut state = state;
+                let mut mapped = Vec::with_capacity(children.len());
+
+                for child in children {
+                    let (next, child) = child.map_accum_at(state, f);
+                    state = next;
+                    mapped.push(child);
                 }
-                .into(),
-            }),
+
+                (state, Tree::Section(mapped))
+            }
         }
     }
 
-    /// Deletes the current node and moves the cursor to a sibling or parent.
+    /// Renders the tree in the Graphviz DOT language.
+    ///
+    /// Every node of the tree becomes a DOT node: items are labeled with
+    /// their value and sections, drawn as boxes, with their index path from
+    /// this tree. Every parent-child relation becomes an edge.
     ///
     /// # Returns
     ///
-    /// * `Some(Location)` - If the deletion was successful.
-    /// * `None` - If the location is at the top.
-    pub fn delete(self) -> Option<Self> {
-        match self.path.as_ref() {
-            Path::Top => None,
-            Path::Node { left, right, path } => {
-                let left = left.as_slice();
-                let right = right.as_slice();
+    /// The DOT source of a `digraph` describing the tree.
+    pub fn to_dot(&self) -> String
+    where
+        T: Display,
+    {
+        let mut dot = String::from("digraph tree {\n");
+        self.write_dot(&mut dot, &mut vec![], &mut 0);
+        dot.push_str("}\n");
+        dot
+    }
 
-                let result = match (left, path, right) {
-                    // In the middle with existing left and right
-                    (left, path, [first_right, rest_right @ ..]) => Self {
-                        cursor: first_right.clone(),
-                        path: crate::Path::Node {
-                            left: left.to_vec(),
-                            right: rest_right.to_vec(),
-                            path: path.clone(),
-                        }
-                        .into(),
-                    },
+    fn write_dot(&self, dot: &mut String, path: &mut Vec<usize>, next_id: &mut usize) -> usize
+    where
+        T: Display,
+    {
+        let id = *next_id;
+        *next_id += 1;
 
-                    // With empty right
-                    ([first_left, rest_left @ ..], path, &[]) => Self {
-                        cursor: first_left.clone(),
-                        path: crate::Path::Node {
-                            left: rest_left.to_vec(),
-                            right: vec![],
-                            path: path.clone(),
-                        }
-                        .into(),
-                    },
-                    // With empty right and left
-                    ([], path, []) => Self {
-                        cursor: Tree::Section(vec![]),
-                        path: path.clone(),
-                    },
-                };
+        match self {
+            Tree::Item(value) => {
+                let label = value.to_string().replace('\\', "\\\\").replace('"', "\\\"");
+                let _ = writeln!(dot, "    n{id} [label=\"{label}\"];");
+            }
+            Tree::Section(children) => {
+                let _ = writeln!(dot, "    n{id} [label=\"{path:?}\", shape=box];");
+
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    let child_id = child.write_dot(dot, path, next_id);
+                    path.pop();
 
-                result.into()
+                    let _ = writeln!(dot, "    n{id} -> n{child_id};");
+                }
             }
         }
+
+        id
     }
-}
 
-#[cfg(test)]
+    /// Walks the tree in preorder, yielding every node together with its siblings.
+    ///
+    /// Each item is a `(left, node, right)` triple holding the node and its
+    /// immediate left and right siblings. The first and last children of a
+    /// section get `None` on the respective side, and so does this tree,
+    /// which is yielded first and has no siblings.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the neighbor triples of every node.
+    pub fn iter_with_neighbors(
+        &self,
+    ) -> impl Iterator<Item = (Option<&Tree<T>>, &Tree<T>, Option<&Tree<T>>)> {
+        let mut stack = vec![(None, self, None)];
+
+        std::iter::from_fn(move || {
+            let (left, tree, right) = stack.pop()?;
+
+            if let Tree::Section(children) = tree {
+                stack.extend((0..children.len()).rev().map(|index| {
+                    (
+                        index.checked_sub(1).map(|left| &children[left]),
+                        &children[index],
+                        children.get(index + 1),
+                    )
+                }));
+            }
+
+            Some((left, tree, right))
+        })
+    }
+
+    /// Measures how unevenly the tree is balanced.
+    ///
+    /// For every section the difference between the tallest and the shortest
+    /// child subtree is taken; the imbalance is the largest of these
+    /// differences. Items and sections with a single child count as balanced.
+    ///
+    /// # Returns
+    ///
+    /// `0` for a perfectly balanced tree, and larger values for more skewed trees.
+    pub fn imbalance(&self) -> usize {
+        self.height_and_imbalance().1
+    }
+
+    fn height_and_imbalance(&self) -> (usize, usize) {
+        match self {
+            Tree::Item(_) => (1, 0),
+            Tree::Section(children) => {
+                let (shortest, tallest, imbalance) = children.iter().fold(
+                    (usize::MAX, 0, 0),
+                    |(shortest, tallest, imbalance), child| {
+                        let (height, child_imbalance) = child.height_and_imbalance();
+                        (
+                            shortest.min(height),
+                            tallest.max(height),
+                            imbalance.max(child_imbalance),
+                        )
+                    },
+                );
+
+                (tallest + 1, imbalance.max(tallest.saturating_sub(shortest)))
+            }
+        }
+    }
+
+    /// Merges the children of two sorted sections into one sorted section.
+    ///
+    /// Both sections must only contain items, each sorted in ascending order;
+    /// the order of the result is unspecified if they are not sorted. Equal
+    /// values keep the children of `self` before those of `other`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The section to merge with.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Tree)` - A section holding the children of both sections in sorted order.
+    /// * `None` - If either tree is an item or has a section among its children.
+    pub fn merge_sorted_children(self, other: Tree<T>) -> Option<Tree<T>>
+    where
+        T: Ord,
+    {
+        fn into_values<T: Clone>(tree: Tree<T>) -> Option<Vec<T>> {
+            match tree {
+                Tree::Item(_) => None,
+                Tree::Section(children) => children
+                    .into_iter()
+                    .map(|child| match child {
+                        Tree::Item(value) => Some(value),
+                        Tree::Section(_) => None,
+                    })
+                    .collect(),
+            }
+        }
+
+        let left = into_values(self)?;
+        let right = into_values(other)?;
+
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        let mut left = left.into_iter().peekable();
+        let mut right = right.into_iter().peekable();
+
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            let next = if r < l { right.next() } else { left.next() };
+            merged.extend(next.map(Tree::Item));
+        }
+
+        merged.extend(left.chain(right).map(Tree::Item));
+
+        Some(Tree::Section(merged))
+    }
+
+    /// Iterates over the item values of the tree in reverse document order.
+    ///
+    /// The traversal walks the tree from the right instead of collecting the
+    /// values first.
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding the values from the last item to the first.
+    pub fn leaves_rev(&self) -> impl Iterator<Item = &T> {
+        let mut stack = vec![self];
+
+        std::iter::from_fn(move || {
+            while let Some(tree) = stack.pop() {
+                match tree {
+                    Tree::Item(value) => return Some(value),
+                    Tree::Section(children) => stack.extend(children),
+                }
+            }
+
+            None
+        })
+    }
+
+    /// Replaces every section holding a single item with that item.
+    ///
+    /// The tree is processed bottom-up, so a chain of nested single-child
+    /// sections ending in an item, such as `Section([Section([Item(x)])])`,
+    /// collapses entirely into `Item(x)`. Sections left with several
+    /// children, a single section child, or no children at all are kept.
+    ///
+    /// # Returns
+    ///
+    /// The tree without single-item sections.
+    pub fn unwrap_single_item_sections(self) -> Tree<T> {
+        match self {
+            Tree::Item(value) => Tree::Item(value),
+            Tree::Section(children) => {
+                let mut children = children
+                    .into_iter()
+                    .map(Tree::unwrap_single_item_sections)
+                    .collect::<Vec<Tree<T>>>();
+
+                match children.as_slice() {
+                    [Tree::Item(_)] => children.remove(0),
+                    _ => Tree::Section(children),
+                }
+            }
+        }
+    }
+
+    /// Converts the tree into the `NestedVec` representation.
+    ///
+    /// # Returns
+    ///
+    /// A `NestedVec` with a `Leaf` for every item and a `Branch` for every section.
+    pub fn to_nested_vec(&self) -> NestedVec<T> {
+        match self {
+            Tree::Item(value) => NestedVec::Leaf(value.clone()),
+            Tree::Section(children) => {
+                NestedVec::Branch(children.iter().map(Tree::to_nested_vec).collect())
+            }
+        }
+    }
+
+    /// Creates a tree from the `NestedVec` representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `nested` - The nested vectors to convert.
+    ///
+    /// # Returns
+    ///
+    /// A tree with an item for every `Leaf` and a section for every `Branch`.
+    pub fn from_nested_vec(nested: NestedVec<T>) -> Tree<T> {
+        match nested {
+            NestedVec::Leaf(value) => Tree::Item(value),
+            NestedVec::Branch(children) => {
+                Tree::Section(children.into_iter().map(Tree::from_nested_vec).collect())
+            }
+        }
+    }
+
+    fn items(&self) -> impl Iterator<Item = &T> {
+        let mut stack = vec![self];
+
+        std::iter::from_fn(move || {
+            while let Some(tree) = stack.pop() {
+                match tree {
+                    Tree::Item(value) => return Some(value),
+                    Tree::Section(children) => stack.extend(children.iter().rev()),
+                }
+            }
+
+            None
+        })
+    }
+
+    fn collect_range(
+        &self,
+        path: &mut Vec<usize>,
+        first: &[usize],
+        last: &[usize],
+        selection: &mut Vec<Tree<T>>,
+    ) {
+        let ends_before_first = path.as_slice() < first && !first.starts_with(path);
+        let starts_after_last = path.as_slice() > last && !path.starts_with(last);
+        if ends_before_first || starts_after_last {
+            return;
+        }
+
+        let ends_within_last =
+            path.starts_with(last) || (path.as_slice() < last && !last.starts_with(path));
+        if path.as_slice() >= first && ends_within_last {
+            selection.push(self.clone());
+            return;
+        }
+
+        if let Tree::Section(children) = self {
+            for (index, child) in children.iter().enumerate() {
+                path.push(index);
+                child.collect_range(path, first, last, selection);
+                path.pop();
+            }
+        }
+    }
+
+    /// Rewrites the tree bottom-up until no rule applies anymore.
+    ///
+    /// Children are rewritten before their parent, and whenever `rule`
+    /// returns a replacement, the replacement is rewritten again, so the
+    /// result is a fixed point of `rule`. To guard against rules that never
+    /// settle, at most [`Tree::REWRITE_LIMIT`] replacements are made; after
+    /// that the tree is returned as it is.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - Returns the replacement for a node, or `None` to keep it.
+    ///
+    /// # Returns
+    ///
+    /// The rewritten tree.
+    pub fn rewrite<F: Fn(&Tree<T>) -> Option<Tree<T>>>(self, rule: F) -> Tree<T> {
+        let mut budget = Self::REWRITE_LIMIT;
+        self.rewrite_at(&rule, &mut budget)
+    }
+
+    fn rewrite_at<F: Fn(&Tree<T>) -> Option<Tree<T>>>(
+        self,
+        rule: &F,
+        budget: &mut usize,
+    ) -> Tree<T> {
+        let mut tree = self.rewrite_children(rule, budget);
+
+        while *budget > 0
+            && let Some(replacement) = rule(&tree)
+        {
+            *budget -= 1;
+            tree = replacement.rewrite_children(rule, budget);
+        }
+
+        tree
+    }
+
+    fn rewrite_children<F: Fn(&Tree<T>) -> Option<Tree<T>>>(
+        self,
+        rule: &F,
+        budget: &mut usize,
+    ) -> Tree<T> {
+        match self {
+            Tree::Item(value) => Tree::Item(value),
+            Tree::Section(children) => Tree::Section(
+                children
+                    .into_iter()
+                    .map(|child| child.rewrite_at(rule, budget))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Counts the nodes at every depth of the tree.
+    ///
+    /// The root is at depth 0 and the children of a node at depth `d` are at
+    /// depth `d + 1`. Sections count as nodes just like items.
+    ///
+    /// # Returns
+    ///
+    /// A vector where index `d` holds the number of nodes at depth `d`. Its
+    /// length is `height + 1`, where `height` is the depth of the deepest
+    /// node, so a lone item or an empty section gives a single entry.
+    pub fn depth_histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![];
+        let mut level = vec![self];
+
+        while !level.is_empty() {
+            histogram.push(level.len());
+            level = level
+                .into_iter()
+                .flat_map(|tree| match tree {
+                    Tree::Item(_) => [].iter(),
+                    Tree::Section(children) => children.iter(),
+                })
+                .collect();
+        }
+
+        histogram
+    }
+
+    /// Transforms every item value, keeping the shape of the tree.
+    ///
+    /// Items are visited in preorder, so `f` sees the values from left to right.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Converts an item value into the new value type.
+    ///
+    /// # Returns
+    ///
+    /// A tree with the same sections and the converted items.
+    pub fn map<U: Clone, F: FnMut(T) -> U>(self, mut f: F) -> Tree<U> {
+        self.map_with(&mut f)
+    }
+
+    fn map_with<U: Clone, F: FnMut(T) -> U>(self, f: &mut F) -> Tree<U> {
+        match self {
+            Tree::Item(value) => Tree::Item(f(value)),
+            Tree::Section(children) => Tree::Section(
+                children
+                    .into_iter()
+                    .map(|child| child.map_with(f))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Calls a function on every node of the tree in preorder, allowing in-place edits.
+    ///
+    /// `f` receives every node before its children, so it may change item
+    /// values or edit a section's children before they are visited. For a
+    /// section, only as many children as it had before `f` ran are visited
+    /// afterwards, so children appended during the visit are never visited
+    /// themselves and the walk always terminates.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Receives a mutable reference to every node.
+    pub fn visit_mut<F: FnMut(&mut Tree<T>)>(&mut self, mut f: F) {
+        self.visit_mut_with(&mut f);
+    }
+
+    fn visit_mut_with<F: FnMut(&mut Tree<T>)>(&mut self, f: &mut F) {
+        let count = match self {
+            Tree::Item(_) => 0,
+            Tree::Section(children) => children.len(),
+        };
+
+        f(self);
+
+        if let Tree::Section(children) = self {
+            children
+                .iter_mut()
+                .take(count)
+                .for_each(|child| child.visit_mut_with(f));
+        }
+    }
+
+    /// Counts the nodes of the tree, items and sections alike.
+    ///
+    /// # Returns
+    ///
+    /// The total number of nodes, including this one.
+    pub fn size(&self) -> usize {
+        match self {
+            Tree::Item(_) => 1,
+            Tree::Section(children) => 1 + children.iter().map(Tree::size).sum::<usize>(),
+        }
+    }
+
+    /// Measures the maximum nesting depth of the tree.
+    ///
+    /// An item and an empty section both have height 1; a section is one
+    /// higher than its highest child.
+    ///
+    /// # Returns
+    ///
+    /// The number of levels of the tree.
+    pub fn height(&self) -> usize {
+        self.height_and_imbalance().0
+    }
+
+    /// Builds a trie from values labeled with key paths.
+    ///
+    /// Only non-terminal key prefixes, the ones that longer key paths
+    /// continue from, become sections. A terminal segment, which ends a key
+    /// path with no longer key paths under it, becomes a bare item holding
+    /// the value: `trie_from(vec![(vec!["c"], 3)])` is `Section([Item(3)])`.
+    /// Entries whose keys share a prefix share the section of that prefix,
+    /// and children are ordered by the first entry reaching them. When a key
+    /// path is also the prefix of longer key paths, its value becomes the
+    /// first child of that prefix's section. When the same key path occurs
+    /// several times, the last value wins. The root is always a section,
+    /// holding the value of an empty key path first, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The key paths and their values.
+    ///
+    /// # Returns
+    ///
+    /// A tree with one section per non-terminal key prefix and one item per distinct key path.
+    pub fn trie_from<K: Eq + Clone>(entries: Vec<(Vec<K>, T)>) -> Tree<T> {
+        let (value, children) = Self::trie_level(entries);
+        Tree::Section(value.map(Tree::Item).into_iter().chain(children).collect())
+    }
+
+    fn trie_level<K: Eq + Clone>(entries: Vec<(Vec<K>, T)>) -> (Option<T>, Vec<Tree<T>>) {
+        let mut value = None;
+        let mut groups: Vec<(K, Vec<_>)> = vec![];
+
+        for (mut key, entry) in entries {
+            if key.is_empty() {
+                value = Some(entry);
+                continue;
+            }
+
+            let segment = key.remove(0);
+            match groups.iter_mut().find(|(group, _)| *group == segment) {
+                Some((_, group)) => group.push((key, entry)),
+                None => groups.push((segment, vec![(key, entry)])),
+            }
+        }
+
+        let children = groups
+            .into_iter()
+            .map(|(_, group)| match Self::trie_level(group) {
+                (Some(value), children) if children.is_empty() => Tree::Item(value),
+                (value, children) => {
+                    Tree::Section(value.map(Tree::Item).into_iter().chain(children).collect())
+                }
+            })
+            .collect();
+
+        (value, children)
+    }
+
+    fn find_indices<F: Fn(&T) -> bool>(&self, pred: &F, indices: &mut Vec<usize>) -> bool {
+        match self {
+            Tree::Item(value) => pred(value),
+            Tree::Section(children) => children.iter().enumerate().any(|(index, child)| {
+                indices.push(index);
+                let found = child.find_indices(pred, indices);
+                if !found {
+                    indices.pop();
+                }
+                found
+            }),
+        }
+    }
+
+    /// Combines another tree of the same shape into this one, in place.
+    ///
+    /// Two trees have the same shape when they are both items, or both
+    /// sections with the same number of children of the same shape. The
+    /// shapes are compared before anything is mutated.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The tree overlaid onto this one.
+    /// * `f` - Updates an item value with the value at the same position in `other`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the shapes match and every item was updated.
+    /// * `Err(ShapeMismatch)` - If the shapes differ; the tree is left unchanged.
+    pub fn zip_mut<U: Clone, F: FnMut(&mut T, &U)>(
+        &mut self,
+        other: &Tree<U>,
+        mut f: F,
+    ) -> Result<(), ShapeMismatch> {
+        if !self.has_shape_of(other) {
+            return Err(ShapeMismatch);
+        }
+
+        self.zip_mut_with(other, &mut f);
+        Ok(())
+    }
+
+    fn has_shape_of<U: Clone>(&self, other: &Tree<U>) -> bool {
+        match (self, other) {
+            (Tree::Item(_), Tree::Item(_)) => true,
+            (Tree::Section(children), Tree::Section(others)) => {
+                children.len() == others.len()
+                    && children
+                        .iter()
+                        .zip(others)
+                        .all(|(child, other)| child.has_shape_of(other))
+            }
+            _ => false,
+        }
+    }
+
+    fn zip_mut_with<U: Clone, F: FnMut(&mut T, &U)>(&mut self, other: &Tree<U>, f: &mut F) {
+        match (self, other) {
+            (Tree::Item(value), Tree::Item(other)) => f(value, other),
+            (Tree::Section(children), Tree::Section(others)) => children
+                .iter_mut()
+                .zip(others)
+                .for_each(|(child, other)| child.zip_mut_with(other, f)),
+            _ => {}
+        }
+    }
+
+    /// Combines two trees of the same shape into a new tree, pairing their items.
+    ///
+    /// The trees match when, at every position, both nodes are items, or
+    /// both are sections with the same number of children that match
+    /// pairwise. Empty sections match each other. An item facing a section,
+    /// or two sections with a different number of children, anywhere in the
+    /// trees is a mismatch.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The tree paired with this one.
+    /// * `f` - Combines an item value with the value at the same position in `other`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Tree)` - The combined tree, if the shapes match.
+    /// * `None` - If the shapes differ.
+    pub fn zip_with<U: Clone, V: Clone, F: FnMut(&T, &U) -> V>(
+        &self,
+        other: &Tree<U>,
+        mut f: F,
+    ) -> Option<Tree<V>> {
+        self.zip_with_at(other, &mut f)
+    }
+
+    fn zip_with_at<U: Clone, V: Clone, F: FnMut(&T, &U) -> V>(
+        &self,
+        other: &Tree<U>,
+        f: &mut F,
+    ) -> Option<Tree<V>> {
+        match (self, other) {
+            (Tree::Item(value), Tree::Item(other)) => Some(Tree::Item(f(value, other))),
+            (Tree::Section(children), Tree::Section(others)) if children.len() == others.len() => {
+                children
+                    .iter()
+                    .zip(others)
+                    .map(|(child, other)| child.zip_with_at(other, f))
+                    .collect::<Option<Vec<Tree<V>>>>()
+                    .map(Tree::Section)
+            }
+            _ => None,
+        }
+    }
+
+    /// Locates every empty section of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The index paths of all empty sections, in preorder. The root itself
+    /// is reported as the empty path if it is an empty section.
+    pub fn find_empty_sections(&self) -> Vec<Vec<usize>> {
+        let mut found = vec![];
+        self.find_empty_sections_at(&mut vec![], &mut found);
+        found
+    }
+
+    fn find_empty_sections_at(&self, path: &mut Vec<usize>, found: &mut Vec<Vec<usize>>) {
+        match self {
+            Tree::Item(_) => {}
+            Tree::Section(children) if children.is_empty() => found.push(path.clone()),
+            Tree::Section(children) => {
+                for (index, child) in children.iter().enumerate() {
+                    path.push(index);
+                    child.find_empty_sections_at(path, found);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    /// Renders the tree as an indented outline.
+    ///
+    /// Every item is written on its own line. A section opens with `[` and
+    /// closes with `]` on their own lines, with its children indented one
+    /// level deeper in between; an empty section is written as `[]`. Lines
+    /// are separated by `\n`, without a trailing newline.
+    ///
+    /// # Arguments
+    ///
+    /// * `indent` - The number of spaces added for every level of nesting.
+    ///
+    /// # Returns
+    ///
+    /// The outline of the tree.
+    pub fn pretty(&self, indent: usize) -> String
+    where
+        T: Display,
+    {
+        let mut outline = String::new();
+        self.write_pretty(&mut outline, indent, 0);
+        outline.pop();
+        outline
+    }
+
+    fn write_pretty(&self, outline: &mut String, indent: usize, level: usize)
+    where
+        T: Display,
+    {
+        let padding = " ".repeat(indent * level);
+
+        match self {
+            Tree::Item(value) => {
+                let _ = writeln!(outline, "{padding}{value}");
+            }
+            Tree::Section(children) if children.is_empty() => {
+                let _ = writeln!(outline, "{padding}[]");
+            }
+            Tree::Section(children) => {
+                let _ = writeln!(outline, "{padding}[");
+                for child in children {
+                    child.write_pretty(outline, indent, level + 1);
+                }
+                let _ = writeln!(outline, "{padding}]");
+            }
+        }
+    }
+
+    /// Collects references to every item value, from left to right.
+    ///
+    /// Sections are not collected themselves, only their items.
+    ///
+    /// # Returns
+    ///
+    /// The item values in preorder.
+    pub fn leaves(&self) -> Vec<&T> {
+        self.items().collect()
+    }
+
+    /// Collects every item value from left to right, consuming the tree.
+    ///
+    /// Like [`Tree::leaves`], but moves the values out instead of borrowing them.
+    ///
+    /// # Returns
+    ///
+    /// The item values in preorder.
+    pub fn into_leaves(self) -> Vec<T> {
+        let mut leaves = vec![];
+        let mut stack = vec![self];
+
+        while let Some(tree) = stack.pop() {
+            match tree {
+                Tree::Item(value) => leaves.push(value),
+                Tree::Section(children) => stack.extend(children.into_iter().rev()),
+            }
+        }
+
+        leaves
+    }
+}
+
+impl<F: Clone> Tree<F> {
+    /// Applies a tree of functions to a tree of values of the same shape.
+    ///
+    /// Every item function is applied to the value at the same position in
+    /// `values`. The shapes are compared as in [`Tree::zip_with`].
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The tree of values the functions are applied to.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Tree)` - The tree of results, if the shapes match.
+    /// * `None` - If the shapes differ.
+    pub fn zip_apply<T: Clone, U: Clone>(&self, values: &Tree<T>) -> Option<Tree<U>>
+    where
+        F: Fn(&T) -> U,
+    {
+        self.zip_with(values, |f, value| f(value))
+    }
+}
+
+impl<T: Clone + Display> Display for Tree<T> {
+    /// Writes the outline of the tree, see [`Tree::pretty`], indented by two spaces per level.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pretty(2))
+    }
+}
+
+impl<T: Clone> From<Vec<Tree<T>>> for Tree<T> {
+    fn from(children: Vec<Tree<T>>) -> Self {
+        Tree::Section(children)
+    }
+}
+
+impl<T: Clone> FromIterator<T> for Tree<T> {
+    /// Collects values into a section of items.
+    ///
+    /// Only a single flat level is built: every value becomes an item child
+    /// of the resulting section.
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
+        Tree::Section(values.into_iter().map(Tree::Item).collect())
+    }
+}
+
+impl<T: Clone> Default for Tree<T> {
+    /// Returns an empty section, so `std::mem::take` leaves `Tree::Section(vec![])` behind.
+    fn default() -> Self {
+        Tree::Section(vec![])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Clone + serde::Serialize> Tree<T> {
+    /// Serializes the tree into indented JSON.
+    ///
+    /// Every node is written as an object with a single key naming its
+    /// variant: `{"Item": value}` or `{"Section": [children]}`, indented by
+    /// two spaces. This is the representation `Deserialize` reads back, and
+    /// it only depends on the shape of the tree, which keeps diffs minimal.
+    ///
+    /// # Returns
+    ///
+    /// The JSON text of the tree.
+    ///
+    /// # Panics
+    ///
+    /// If serializing an item value fails.
+    pub fn to_pretty_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("item values must serialize to JSON")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a tree as plain nested vectors.
+///
+/// This is the leaf/branch shape expected by code working with
+/// vectors of vectors; see `Tree::to_nested_vec` and `Tree::from_nested_vec`.
+pub enum NestedVec<T: Clone> {
+    /// A single value.
+    Leaf(T),
+    /// A vector of nested values.
+    Branch(Vec<NestedVec<T>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents a path within a tree, used for navigation and context tracking.
+///
+/// The path keeps track of the location in the tree structure,
+/// remembering the siblings to the left and right of the current position,
+/// as well as the path to the parent.
+pub enum Path<T: Clone> {
+    /// Represents the top level of the tree hierarchy.
+    Top,
+    /// Represents a position within the tree structure.
+    Node {
+        /// Trees to the left of the current position.
+        left: Vec<Tree<T>>,
+        /// Trees to the right of the current position.
+        right: Vec<Tree<T>>,
+        /// Path to the parent node.
+        path: Rc<Path<T>>,
+    },
+}
+
+/// The borrowed `(left, right)` siblings of one `Path::Node`, see [`Location::context`].
+pub type Siblings<'a, T> = (&'a [Tree<T>], &'a [Tree<T>]);
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents a location (cursor) within a tree.
+///
+/// A location combines a cursor pointing to the current tree node
+/// and a path providing context for navigation within the overall tree structure.
+pub struct Location<T: Clone> {
+    /// The current tree node being focused on.
+    pub cursor: Tree<T>,
+    /// The path representing the context of this location within the overall tree.
+    pub path: Rc<Path<T>>,
+}
+
+impl<T: Clone> Location<T> {
+    /// Creates a new location from a tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to create a location from.
+    ///
+    /// # Returns
+    ///
+    /// A new `Location` instance with the given tree as cursor.
+    pub fn new(tree: Tree<T>) -> Self {
+        Self {
+            cursor: tree.clone(),
+            path: Path::Node {
+                left: vec![],
+                right: vec![tree.clone()],
+                path: Rc::new(Path::Top),
+            }
+            .into(),
+        }
+    }
+
+    /// Creates a location focused on a tree as the true root, at `Path::Top`.
+    ///
+    /// Unlike [`Location::new`], which nests the tree under a parent section
+    /// holding a copy of it, the location starts at the top: `go_up` returns
+    /// `None` straight away and `go_down` enters the first child of `tree`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to create a location from.
+    ///
+    /// # Returns
+    ///
+    /// A new `Location` instance with the given tree as cursor.
+    pub fn rooted(tree: Tree<T>) -> Self {
+        Self {
+            cursor: tree,
+            path: Path::Top.into(),
+        }
+    }
+
+    /// Moves the cursor to the left sibling.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a left sibling.
+    /// * `None` - If there is no left sibling or the location is at the top.
+    pub fn go_left(self) -> Option<Self> {
+        self.try_go_left().ok()
+    }
+
+    /// Moves the cursor to the left sibling, describing why the move failed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Location)` - If there is a left sibling.
+    /// * `Err(NavError::AtTop)` - If the location is at the top.
+    /// * `Err(NavError::NoSibling)` - If there is no left sibling.
+    pub fn try_go_left(self) -> Result<Self, NavError> {
+        match self.path.as_ref() {
+            Path::Top => Err(NavError::AtTop),
+            Path::Node { left, right, path } => left
+                .split_first()
+                .map(|(first, rest)| Self {
+                    cursor: first.clone(),
+                    path: Path::Node {
+                        left: rest.to_vec(),
+                        path: path.clone(),
+                        right: vec![self.cursor].into_iter().chain(right.clone()).collect(),
+                    }
+                    .into(),
+                })
+                .ok_or(NavError::NoSibling),
+        }
+    }
+
+    /// Moves the cursor to the right sibling.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a right sibling.
+    /// * `None` - If there is no right sibling or the location is at the top.
+    pub fn go_right(self) -> Option<Self> {
+        self.try_go_right().ok()
+    }
+
+    /// Moves the cursor to the right sibling, describing why the move failed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Location)` - If there is a right sibling.
+    /// * `Err(NavError::AtTop)` - If the location is at the top.
+    /// * `Err(NavError::NoSibling)` - If there is no right sibling.
+    pub fn try_go_right(self) -> Result<Self, NavError> {
+        match self.path.as_ref() {
+            Path::Top => Err(NavError::AtTop),
+            Path::Node { left, right, path } => right
+                .split_first()
+                .map(|(first, rest)| Self {
+                    cursor: first.clone(),
+                    path: Path::Node {
+                        left: vec![self.cursor].into_iter().chain(left.clone()).collect(),
+                        right: rest.to_vec(),
+                        path: path.clone(),
+                    }
+                    .into(),
+                })
+                .ok_or(NavError::NoSibling),
+        }
+    }
+
+    /// Moves the cursor to the parent node.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a parent node.
+    /// * `None` - If the location is at the top.
+    pub fn go_up(self) -> Option<Self> {
+        self.try_go_up().ok()
+    }
+
+    /// Moves the cursor to the parent node, describing why the move failed.
+    ///
+    /// The cursor is moved into the rebuilt section. When this location holds
+    /// the only reference to its path node the siblings are moved as well,
+    /// otherwise (for example after [`Location::fork`]) they are cloned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Location)` - If there is a parent node.
+    /// * `Err(NavError::AtTop)` - If the location is at the top.
+    pub fn try_go_up(self) -> Result<Self, NavError> {
+        let (mut left, right, path) = match Rc::try_unwrap(self.path) {
+            Ok(Path::Top) => return Err(NavError::AtTop),
+            Ok(Path::Node { left, right, path }) => (left, right, path),
+            Err(shared) => match shared.as_ref() {
+                Path::Top => return Err(NavError::AtTop),
+                Path::Node { left, right, path } => (left.clone(), right.clone(), path.clone()),
+            },
+        };
+
+        left.reverse();
+        let mut children = left;
+        children.reserve(right.len() + 1);
+        children.push(self.cursor);
+        children.extend(right);
+
+        Ok(Self {
+            path,
+            cursor: Tree::Section(children),
+        })
+    }
+
+    /// Moves the cursor to the first child node.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the current node is a section with at least one child.
+    /// * `None` - If the current node is an item or an empty section.
+    pub fn go_down(self) -> Option<Self> {
+        self.try_go_down().ok()
+    }
+
+    /// Moves the cursor to the first child node, describing why the move failed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Location)` - If the current node is a section with at least one child.
+    /// * `Err(NavError::NotASection)` - If the current node is an item.
+    /// * `Err(NavError::EmptySection)` - If the current node is an empty section.
+    pub fn try_go_down(self) -> Result<Self, NavError> {
+        match self.cursor {
+            Tree::Item(_) => Err(NavError::NotASection),
+            Tree::Section(trees) => trees
+                .split_first()
+                .map(|(first, rest)| Self {
+                    cursor: first.clone(),
+                    path: Path::Node {
+                        left: vec![],
+                        right: rest.into(),
+                        path: self.path,
+                    }
+                    .into(),
+                })
+                .ok_or(NavError::EmptySection),
+        }
+    }
+
+    /// Gets the nth child of the current node.
+    ///
+    /// This is equivalent to n calls to `go_right()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the child to navigate to.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the child exists.
+    /// * `None` - If the child doesn't exist or the current node is an item.
+    pub fn get_nth(self, n: usize) -> Option<Self> {
+        match n {
+            0 => self.go_down(),
+            n => self.get_nth(n - 1).and_then(Location::go_right),
+        }
+    }
+
+    /// Replaces the current node with a new tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The new tree to replace the current node with.
+    ///
+    /// # Returns
+    ///
+    /// A new location with the updated cursor.
+    pub fn change(self, tree: Tree<T>) -> Self {
+        Self {
+            cursor: tree,
+            path: self.path,
+        }
+    }
+
+    /// Inserts a new tree to the right of the current node.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to insert.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the insertion was successful.
+    /// * `None` - If the location is at the top.
+    pub fn insert_right(self, tree: Tree<T>) -> Option<Self> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, right, path } => Self {
+                cursor: self.cursor.clone(),
+                path: Path::Node {
+                    left: left.clone(),
+                    path: path.clone(),
+                    right: vec![tree].into_iter().chain(right.clone()).collect(),
+                }
+                .into(),
+            }
+            .into(),
+        }
+    }
+
+    /// Inserts a new tree to the left of the current node.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to insert.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the insertion was successful.
+    /// * `None` - If the location is at the top.
+    pub fn insert_left(self, tree: Tree<T>) -> Option<Self> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, right, path } => Self {
+                cursor: self.cursor.clone(),
+                path: Path::Node {
+                    left: vec![tree].into_iter().chain(left.clone()).collect(),
+                    right: right.to_vec(),
+                    path: path.clone(),
+                }
+                .into(),
+            }
+            .into(),
+        }
+    }
+
+    /// Inserts a new tree as the first child of the current node.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to insert.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the current node is a section.
+    /// * `None` - If the current node is an item.
+    pub fn insert_down(self, tree: Tree<T>) -> Option<Self> {
+        match self.cursor {
+            Tree::Item(_) => None,
+            Tree::Section(children) => Some(Self {
+                cursor: tree,
+                path: Path::Node {
+                    left: vec![],
+                    right: children,
+                    path: self.path,
+                }
+                .into(),
+            }),
+        }
+    }
+
+    /// Deletes the current node and moves the cursor to a sibling or parent.
+    ///
+    /// The cursor moves to the right sibling if there is one, otherwise to
+    /// the left sibling. When the deleted node was the only child, the cursor
+    /// moves up to its parent, which is now an empty section and keeps its
+    /// own place and siblings.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the deletion was successful.
+    /// * `None` - If the location is at the top.
+    pub fn delete(self) -> Option<Self> {
+        self.delete_returning().map(|(location, _)| location)
+    }
+
+    /// Deletes the current node like [`Location::delete`], handing back the deleted subtree.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((Location, Tree))` - The new location and the deleted subtree.
+    /// * `None` - If the location is at the top.
+    pub fn delete_returning(self) -> Option<(Self, Tree<T>)> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, right, path } => {
+                let left = left.as_slice();
+                let right = right.as_slice();
+
+                let result = match (left, path, right) {
+                    // In the middle with existing left and right
+                    (left, path, [first_right, rest_right @ ..]) => Self {
+                        cursor: first_right.clone(),
+                        path: crate::Path::Node {
+                            left: left.to_vec(),
+                            right: rest_right.to_vec(),
+                            path: path.clone(),
+                        }
+                        .into(),
+                    },
+
+                    // With empty right
+                    ([first_left, rest_left @ ..], path, &[]) => Self {
+                        cursor: first_left.clone(),
+                        path: crate::Path::Node {
+                            left: rest_left.to_vec(),
+                            right: vec![],
+                            path: path.clone(),
+                        }
+                        .into(),
+                    },
+                    // With empty right and left
+                    ([], path, []) => Self {
+                        cursor: Tree::Section(vec![]),
+                        path: path.clone(),
+                    },
+                };
+
+                Some((result, self.cursor))
+            }
+        }
+    }
+
+    /// Applies a sequence of moves described by a compact command string.
+    ///
+    /// Each command is one of `d` (down), `u` (up), `l` (left) or `r` (right),
+    /// optionally prefixed by a repeat count, so `"d2r"` goes down and then
+    /// twice to the right. Whitespace between commands is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - The command string to interpret.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If every move succeeded.
+    /// * `None` - If a move failed, or the string contains an unknown command
+    ///   or a trailing count without a command.
+    pub fn navigate(self, commands: &str) -> Option<Self> {
+        let mut location = self;
+        let mut count: Option<usize> = None;
+
+        for c in commands.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                count = Some(
+                    count
+                        .unwrap_or(0)
+                        .saturating_mul(10)
+                        .saturating_add(digit as usize),
+                );
+                continue;
+            }
+
+            if c.is_whitespace() {
+                continue;
+            }
+
+            let step: fn(Self) -> Option<Self> = match c {
+                'd' => Location::go_down,
+                'u' => Location::go_up,
+                'l' => Location::go_left,
+                'r' => Location::go_right,
+                _ => return None,
+            };
+
+            for _ in 0..count.take().unwrap_or(1) {
+                location = step(location)?;
+            }
+        }
+
+        count.is_none().then_some(location)
+    }
+
+    /// Returns the value of the cursor if it is an item.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&T)` - If the cursor is a `Tree::Item`.
+    /// * `None` - If the cursor is a section.
+    pub fn as_item(&self) -> Option<&T> {
+        match &self.cursor {
+            Tree::Item(value) => Some(value),
+            Tree::Section(_) => None,
+        }
+    }
+
+    /// Returns the children of the cursor if it is a section.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[Tree<T>])` - If the cursor is a `Tree::Section`.
+    /// * `None` - If the cursor is an item.
+    pub fn as_section(&self) -> Option<&[Tree<T>]> {
+        match &self.cursor {
+            Tree::Item(_) => None,
+            Tree::Section(children) => Some(children),
+        }
+    }
+
+    /// Returns the children of the cursor if it is a section, same as [`Location::as_section`].
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[Tree])` - If the cursor is a `Tree::Section`.
+    /// * `None` - If the cursor is an item.
+    pub fn as_children(&self) -> Option<&[Tree<T>]> {
+        self.as_section()
+    }
+
+    /// Takes the value out of the cursor if it is an item.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - If the cursor is a `Tree::Item`.
+    /// * `Err(Location)` - The unchanged location, if the cursor is a section.
+    pub fn into_item(self) -> Result<T, Self> {
+        match self.cursor {
+            Tree::Item(value) => Ok(value),
+            cursor => Err(Self {
+                cursor,
+                path: self.path,
+            }),
+        }
+    }
+
+    /// Takes the children out of the cursor if it is a section.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Tree>)` - If the cursor is a `Tree::Section`.
+    /// * `Err(Location)` - The unchanged location, if the cursor is an item.
+    pub fn into_section(self) -> Result<Vec<Tree<T>>, Self> {
+        match self.cursor {
+            Tree::Section(children) => Ok(children),
+            cursor => Err(Self {
+                cursor,
+                path: self.path,
+            }),
+        }
+    }
+
+    /// Moves the focused node to another section of the tree.
+    ///
+    /// The node is cut from its current position and inserted as child
+    /// `position` of the section at `target_parent`. Both are expressed as
+    /// index paths from the root of the tree, and `target_parent` refers to
+    /// the tree as it was before the node was cut; `position` counts the
+    /// children of the target once the node has been removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_parent` - The index path of the section receiving the node.
+    /// * `position` - The child index the node gets in the target section.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the moved node at its new position.
+    /// * `None` - If the location is at the top, the target is not a section,
+    ///   the position is out of bounds, or the target is inside the moved subtree.
+    pub fn move_to(self, target_parent: &[usize], position: usize) -> Option<Self> {
+        let (mut root, indices) = self.rekey();
+        let (source_index, source_parent) = indices.split_last()?;
+
+        if target_parent.starts_with(&indices) {
+            return None;
+        }
+
+        let node = match root.get_mut(source_parent)? {
+            Tree::Section(children) => children.remove(*source_index),
+            Tree::Item(_) => return None,
+        };
+
+        let mut target = target_parent.to_vec();
+        let depth = source_parent.len();
+        if target.len() > depth
+            && target.starts_with(source_parent)
+            && target[depth] > *source_index
+        {
+            target[depth] -= 1;
+        }
+
+        match root.get_mut(&target)? {
+            Tree::Section(children) if position <= children.len() => {
+                children.insert(position, node)
+            }
+            _ => return None,
+        }
+
+        target.push(position);
+        Self::from_root_and_indices(root, &target)
+    }
+
+    /// Reconstructs the root of the tree together with the index path of the cursor.
+    ///
+    /// The index path lists the child index taken at every level from the
+    /// root down to the cursor, so it is empty for a location at the top.
+    /// Together with [`Location::from_root_and_indices`] it turns a location
+    /// into plain data that can be stored and turned back into a location.
+    ///
+    /// # Returns
+    ///
+    /// The root of the tree and the index path of the cursor within it.
+    pub fn rekey(self) -> (Tree<T>, Vec<usize>) {
+        let indices = self.path_indices();
+        (self.go_to_root(), indices)
+    }
+
+    /// Creates a location by following an index path from the root of a tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the tree.
+    /// * `indices` - The child index to take at every level, outermost first.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the node at the end of the index path.
+    /// * `None` - If the index path does not exist in the tree.
+    pub fn from_root_and_indices(root: Tree<T>, indices: &[usize]) -> Option<Self> {
+        Self::rooted(root).navigate_indices(indices)
+    }
+
+    /// Follows an index path down from the current node.
+    ///
+    /// Every index is replayed as `go_down` followed by that many `go_right`
+    /// moves, see [`Location::get_nth`].
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - The child index to take at every level, outermost first.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the node at the end of the index path.
+    /// * `None` - If the index path does not exist below the current node.
+    pub fn navigate_indices(self, indices: &[usize]) -> Option<Self> {
+        indices
+            .iter()
+            .try_fold(self, |location, index| location.get_nth(*index))
+    }
+
+    /// Moves the cursor to the right sibling, wrapping around to the leftmost sibling.
+    ///
+    /// An only child wraps around onto itself.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the right sibling, or on the leftmost
+    ///   sibling if the cursor is the rightmost one.
+    /// * `None` - If the location is at the top.
+    pub fn go_right_wrapping(self) -> Option<Self> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { right, .. } if !right.is_empty() => self.go_right(),
+            Path::Node { .. } => self.go_leftmost(),
+        }
+    }
+
+    /// Moves the cursor to the left sibling, wrapping around to the rightmost sibling.
+    ///
+    /// An only child wraps around onto itself.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the left sibling, or on the rightmost
+    ///   sibling if the cursor is the leftmost one.
+    /// * `None` - If the location is at the top.
+    pub fn go_left_wrapping(self) -> Option<Self> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, .. } if !left.is_empty() => self.go_left(),
+            Path::Node { .. } => self.go_rightmost(),
+        }
+    }
+
+    /// Moves the cursor to the first sibling of the current level.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the leftmost sibling, which is the
+    ///   unchanged location if the cursor is already there.
+    /// * `None` - If the location is at the top.
+    pub fn go_leftmost(self) -> Option<Self> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, .. } if left.is_empty() => Some(self),
+            Path::Node { left, right, path } => {
+                let (leftmost, rest) = left.split_last()?;
+                Some(Self {
+                    cursor: leftmost.clone(),
+                    path: Path::Node {
+                        left: vec![],
+                        right: rest
+                            .iter()
+                            .rev()
+                            .cloned()
+                            .chain([self.cursor])
+                            .chain(right.iter().cloned())
+                            .collect(),
+                        path: path.clone(),
+                    }
+                    .into(),
+                })
+            }
+        }
+    }
+
+    /// Moves the cursor to the last sibling of the current level.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the rightmost sibling, which is the
+    ///   unchanged location if the cursor is already there.
+    /// * `None` - If the location is at the top.
+    pub fn go_rightmost(self) -> Option<Self> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { right, .. } if right.is_empty() => Some(self),
+            Path::Node { left, right, path } => {
+                let (rightmost, rest) = right.split_last()?;
+                Some(Self {
+                    cursor: rightmost.clone(),
+                    path: Path::Node {
+                        left: rest
+                            .iter()
+                            .rev()
+                            .cloned()
+                            .chain([self.cursor])
+                            .chain(left.iter().cloned())
+                            .collect(),
+                        right: vec![],
+                        path: path.clone(),
+                    }
+                    .into(),
+                })
+            }
+        }
+    }
+
+    /// Lazily visits the focused subtree in preorder, yielding a location for every node.
+    ///
+    /// The first location yielded is this one. Every yielded location carries
+    /// its full path, so it can be edited and reassembled like any other.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the locations of the focused node and its descendants.
+    pub fn preorder_locations(self) -> impl Iterator<Item = Location<T>> {
+        self.preorder()
+    }
+
+    /// Visits the focused subtree in depth-first preorder, see [`PreOrder`].
+    ///
+    /// # Returns
+    ///
+    /// A `PreOrder` iterator starting at this location.
+    pub fn preorder(self) -> PreOrder<T> {
+        PreOrder {
+            next: Some((self, 0)),
+        }
+    }
+
+    fn next_preorder(self, depth: usize) -> Option<(Self, usize)> {
+        if matches!(&self.cursor, Tree::Section(children) if !children.is_empty()) {
+            return self.go_down().map(|location| (location, depth + 1));
+        }
+
+        let mut location = self;
+        let mut depth = depth;
+
+        while depth > 0 {
+            if let Path::Node { right, .. } = location.path.as_ref()
+                && !right.is_empty()
+            {
+                return location.go_right().map(|location| (location, depth));
+            }
+
+            location = location.go_up()?;
+            depth -= 1;
+        }
+
+        None
+    }
+
+    /// Rewrites the focused subtree by walking it alongside another tree.
+    ///
+    /// Nodes are visited bottom-up: the children of a section are rewritten
+    /// first, then `f` is called with the rebuilt section and its counterpart.
+    /// The counterpart of a child is the child at the same index in the
+    /// counterpart of its parent; it is `None` when `other` is shorter there
+    /// or holds an item in place of a section.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The tree overlaid onto the cursor.
+    /// * `f` - Produces the new node from a node and its counterpart, if any.
+    ///
+    /// # Returns
+    ///
+    /// A new location with the rewritten cursor.
+    pub fn update_from<F>(self, other: &Tree<T>, f: F) -> Self
+    where
+        F: Fn(&Tree<T>, Option<&Tree<T>>) -> Tree<T>,
+    {
+        Self {
+            cursor: self.cursor.update_from_at(Some(other), &f),
+            path: self.path,
+        }
+    }
+
+    /// Deletes a descendant of the cursor while keeping the focus on the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - The index path of the node to delete, relative to the cursor.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the updated cursor.
+    /// * `None` - If the path is empty (it would target the cursor itself) or
+    ///   does not exist in the focused subtree.
+    pub fn delete_at(mut self, indices: &[usize]) -> Option<Self> {
+        let (index, parent) = indices.split_last()?;
+
+        match self.cursor.get_mut(parent)? {
+            Tree::Section(children) if *index < children.len() => {
+                children.remove(*index);
+            }
+            _ => return None,
+        }
+
+        Some(self)
+    }
+
+    /// Removes an empty section left behind at the cursor, for instance by `delete`.
+    ///
+    /// If the cursor is an empty section that has a parent, it is removed
+    /// and the focus moves to its right sibling, or to its left sibling when
+    /// it was the rightmost child. When it was an only child the focus moves
+    /// to the parent, which is now an empty section itself; it is not removed
+    /// in turn, so call `cleanup` again to keep pruning upwards. Any other
+    /// location is returned unchanged.
+    ///
+    /// # Returns
+    ///
+    /// The location after the cleanup.
+    pub fn cleanup(self) -> Self {
+        match (&self.cursor, self.path.as_ref()) {
+            (Tree::Section(children), Path::Node { .. }) if children.is_empty() => self
+                .delete()
+                .expect("a location below the top can be deleted"),
+            _ => self,
+        }
+    }
+
+    /// Applies `f` to an ancestor of the cursor and then returns to the original position.
+    ///
+    /// The ancestor is reached by moving up `levels_up` times; the focus is
+    /// restored by descending along the same child indices afterwards, so `f`
+    /// must keep those children in place for the descent to succeed.
+    ///
+    /// # Arguments
+    ///
+    /// * `levels_up` - How many levels to go up; `0` applies `f` to the cursor.
+    /// * `f` - Produces the new ancestor from the current one.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the original position in the updated tree.
+    /// * `None` - If the cursor has fewer than `levels_up` ancestors or the
+    ///   original position no longer exists after applying `f`.
+    pub fn map_ancestor<F: FnOnce(Tree<T>) -> Tree<T>>(
+        self,
+        levels_up: usize,
+        f: F,
+    ) -> Option<Self> {
+        let mut location = self;
+        let mut indices = Vec::with_capacity(levels_up);
+
+        for _ in 0..levels_up {
+            if let Path::Node { left, .. } = location.path.as_ref() {
+                indices.push(left.len());
+            }
+            location = location.go_up()?;
+        }
+
+        let location = Self {
+            cursor: f(location.cursor),
+            path: location.path,
+        };
+
+        indices
+            .iter()
+            .rev()
+            .try_fold(location, |location, index| location.get_nth(*index))
+    }
+
+    /// Replaces the current node with a new tree, unless that would break navigation into it.
+    ///
+    /// The replacement is accepted when it keeps the kind of the cursor and
+    /// every child index that was valid before (as used by `get_nth`) stays
+    /// valid: an item may be replaced by any item, and a section by a section
+    /// with at least as many children. Unlike `change`, this never turns an
+    /// item into a section or the other way round, nor drops children.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The new tree to replace the current node with.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Location)` - A new location with the updated cursor.
+    /// * `Err(Tree)` - The rejected tree, if the replacement is not allowed.
+    pub fn change_checked(self, tree: Tree<T>) -> Result<Self, Tree<T>> {
+        let allowed = match (&self.cursor, &tree) {
+            (Tree::Item(_), Tree::Item(_)) => true,
+            (Tree::Section(current), Tree::Section(new)) => new.len() >= current.len(),
+            _ => false,
+        };
+
+        if allowed {
+            Ok(self.change(tree))
+        } else {
+            Err(tree)
+        }
+    }
+
+    /// Returns the node `go_down` would focus, without moving.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Tree)` - The first child of the cursor.
+    /// * `None` - If the cursor is an item or an empty section.
+    pub fn peek_down(&self) -> Option<&Tree<T>> {
+        match &self.cursor {
+            Tree::Item(_) => None,
+            Tree::Section(children) => children.first(),
+        }
+    }
+
+    /// Returns the node `go_left` would focus, without moving.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Tree)` - The left sibling of the cursor.
+    /// * `None` - If there is no left sibling or the location is at the top.
+    pub fn peek_left(&self) -> Option<&Tree<T>> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, .. } => left.first(),
+        }
+    }
+
+    /// Returns the node `go_right` would focus, without moving.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Tree)` - The right sibling of the cursor.
+    /// * `None` - If there is no right sibling or the location is at the top.
+    pub fn peek_right(&self) -> Option<&Tree<T>> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { right, .. } => right.first(),
+        }
+    }
+
+    /// Finds the index of the first child of the cursor matching a predicate.
+    ///
+    /// The index can be passed to `get_nth` to move to that child.
+    ///
+    /// # Arguments
+    ///
+    /// * `pred` - Returns `true` for the child being searched for.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The index of the first matching child.
+    /// * `None` - If no child matches or the cursor is an item.
+    pub fn child_index<F: Fn(&Tree<T>) -> bool>(&self, pred: F) -> Option<usize> {
+        self.as_section()?.iter().position(pred)
+    }
+
+    /// Moves up to the top and returns the reconstructed tree.
+    ///
+    /// The siblings at every level are put back around the cursor, so the
+    /// result is the whole tree including all edits. A location already at
+    /// `Path::Top` returns its cursor unchanged.
+    ///
+    /// # Returns
+    ///
+    /// The root of the tree.
+    pub fn go_to_root(self) -> Tree<T> {
+        self.root_location().cursor
+    }
+
+    /// Moves up to the top of the tree.
+    ///
+    /// # Returns
+    ///
+    /// A location at `Path::Top` focused on the root of the tree.
+    pub fn root_location(self) -> Location<T> {
+        let mut location = self;
+
+        while let Path::Node { .. } = location.path.as_ref() {
+            location = location.go_up().expect("a node always has a parent");
+        }
+
+        location
+    }
+
+    /// Iterates over the item values of the focused subtree.
+    ///
+    /// The subtree is walked depth-first in pre-order, from left to right,
+    /// without collecting the values up front. Empty sections yield nothing.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the values of the items below and including the cursor.
+    pub fn iter_items(&self) -> impl Iterator<Item = &T> {
+        self.cursor.items()
+    }
+
+    /// Counts the items of the focused subtree.
+    ///
+    /// # Returns
+    ///
+    /// The number of values `iter_items` would yield.
+    pub fn count_items(&self) -> usize {
+        self.iter_items().count()
+    }
+
+    /// Collects the subtrees between this location and another one, in document order.
+    ///
+    /// Both locations must belong to the same tree. The selection runs from
+    /// the start of whichever endpoint comes first in document order to the
+    /// end of the other endpoint's subtree, and is returned as the largest
+    /// subtrees lying entirely inside it; sections that are only partially
+    /// covered are split into their covered children. When one endpoint is
+    /// an ancestor of the other, the selection is the ancestor's subtree.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other endpoint of the selection, in either order.
+    ///
+    /// # Returns
+    ///
+    /// The selected subtrees, both endpoints included.
+    pub fn select_to(&self, other: &Self) -> Vec<Tree<T>> {
+        let here = self.path_indices();
+        let there = other.path_indices();
+
+        let (first, last, first_cursor) = if here <= there {
+            (here, there, &self.cursor)
+        } else {
+            (there, here, &other.cursor)
+        };
+
+        if last.starts_with(&first) {
+            return vec![first_cursor.clone()];
+        }
+
+        let mut selection = vec![];
+        self.clone()
+            .go_to_root()
+            .collect_range(&mut vec![], &first, &last, &mut selection);
+        selection
+    }
+
+    /// Returns how deeply the cursor is nested, as the number of
+    /// `Path::Node` frames between it and `Path::Top`.
+    ///
+    /// A location at `Path::Top` has depth 0. Since [`Location::new`] wraps
+    /// the tree in a `Path::Node`, a fresh location reports depth 1.
+    ///
+    /// # Returns
+    ///
+    /// The nesting level of the cursor.
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut path = self.path.as_ref();
+
+        while let Path::Node { path: parent, .. } = path {
+            depth += 1;
+            path = parent;
+        }
+
+        depth
+    }
+
+    /// Returns the position of the cursor among its siblings.
+    ///
+    /// The position is the number of left siblings, so it round-trips with
+    /// [`Location::get_nth`] on the parent.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The index of the cursor within its parent section.
+    /// * `None` - If the location is at the top.
+    pub fn index(&self) -> Option<usize> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, .. } => Some(left.len()),
+        }
+    }
+
+    /// Promotes an item cursor into a section holding that item.
+    ///
+    /// If the cursor is `Item(x)`, it is replaced with `Section([Item(x)])`
+    /// and the cursor moves down, so the focus stays on the original item
+    /// while children can now be inserted next to it. If the cursor is
+    /// already a section, the location is returned unchanged.
+    ///
+    /// # Returns
+    ///
+    /// A location focused on the original item, or the unchanged section.
+    pub fn make_section(self) -> Self {
+        match self.cursor {
+            Tree::Item(value) => Self {
+                cursor: Tree::Item(value),
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![],
+                    path: self.path,
+                }
+                .into(),
+            },
+            section => Self {
+                cursor: section,
+                path: self.path,
+            },
+        }
+    }
+
+    /// Removes a span of siblings next to the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of siblings to remove.
+    /// * `direction` - The side of the cursor to remove them from.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((Vec<Tree>, Location))` - The removed siblings in document
+    ///   order, and the location with the same cursor without them.
+    /// * `None` - If the location is at the top or there are fewer than
+    ///   `count` siblings in that direction.
+    pub fn cut_siblings(self, count: usize, direction: Direction) -> Option<(Vec<Tree<T>>, Self)> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+
+        let (left, right, cut) = match direction {
+            Direction::Left if count <= left.len() => {
+                let mut cut = left[..count].to_vec();
+                cut.reverse();
+                (left[count..].to_vec(), right.clone(), cut)
+            }
+            Direction::Right if count <= right.len() => (
+                left.clone(),
+                right[count..].to_vec(),
+                right[..count].to_vec(),
+            ),
+            _ => return None,
+        };
+
+        let location = Self {
+            cursor: self.cursor,
+            path: Path::Node {
+                left,
+                right,
+                path: path.clone(),
+            }
+            .into(),
+        };
+
+        Some((cut, location))
+    }
+
+    /// Counts the nodes of the focused subtree, see [`Tree::size`].
+    pub fn size(&self) -> usize {
+        self.cursor.size()
+    }
+
+    /// Measures the height of the focused subtree, see [`Tree::height`].
+    pub fn height(&self) -> usize {
+        self.cursor.height()
+    }
+
+    /// Reassembles the whole tree, reserving every rebuilt section up front.
+    ///
+    /// The result is the same as [`Location::go_to_root`], but every section
+    /// on the way up is allocated once with room for all of its children,
+    /// which are known from the path, instead of growing while collecting.
+    ///
+    /// # Returns
+    ///
+    /// The root of the tree.
+    pub fn to_tree_prealloc(self) -> Tree<T> {
+        let mut tree = self.cursor;
+        let mut path = self.path;
+
+        while let Path::Node {
+            left,
+            right,
+            path: parent,
+        } = path.as_ref()
+        {
+            let mut children = Vec::with_capacity(left.len() + 1 + right.len());
+            children.extend(left.iter().rev().cloned());
+            children.push(tree);
+            children.extend(right.iter().cloned());
+
+            tree = Tree::Section(children);
+            path = parent.clone();
+        }
+
+        tree
+    }
+
+    /// Searches the focused subtree for the first item matching a predicate.
+    ///
+    /// The subtree is searched depth-first in preorder, so children are
+    /// searched before right siblings and items are tried from left to right,
+    /// in the order they appear in the tree. Only items are tested; sections
+    /// are descended into.
+    ///
+    /// # Arguments
+    ///
+    /// * `pred` - Returns true for the item value being searched for.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Location)` - Focused on the first matching item.
+    /// * `Err(Location)` - The unchanged location, if no item matches.
+    pub fn find<F: Fn(&T) -> bool>(self, pred: F) -> Result<Self, Self> {
+        let mut indices = vec![];
+        if !self.cursor.find_indices(&pred, &mut indices) {
+            return Err(self);
+        }
+
+        Ok(indices.into_iter().fold(self, |location, index| {
+            location
+                .get_nth(index)
+                .expect("the index path was taken from the subtree")
+        }))
+    }
+
+    /// Replaces the current node with a new tree, handing back the old one.
+    ///
+    /// Unlike [`Location::change`], the previously focused subtree is returned,
+    /// which is useful for undo.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The new tree to replace the current node with.
+    ///
+    /// # Returns
+    ///
+    /// A new location with the updated cursor, and the replaced subtree.
+    pub fn replace(self, tree: Tree<T>) -> (Self, Tree<T>) {
+        (
+            Self {
+                cursor: tree,
+                path: self.path,
+            },
+            self.cursor,
+        )
+    }
+
+    /// Returns a copy of the location for speculative edits, same as `self.clone()`.
+    pub fn fork(&self) -> Location<T> {
+        self.clone()
+    }
+
+    /// Mutates the current node in place.
+    ///
+    /// The path is left untouched, so only the focused subtree changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Receives a mutable reference to the cursor.
+    ///
+    /// # Returns
+    ///
+    /// A new location with the mutated cursor.
+    pub fn modify<F: FnOnce(&mut Tree<T>)>(mut self, f: F) -> Self {
+        f(&mut self.cursor);
+        self
+    }
+
+    /// Visits the focused subtree level by level, see [`BreadthFirst`].
+    ///
+    /// # Returns
+    ///
+    /// A `BreadthFirst` iterator starting at the cursor.
+    pub fn breadth_first(self) -> BreadthFirst<T> {
+        BreadthFirst {
+            queue: VecDeque::from([self.cursor]),
+        }
+    }
+
+    /// Starts a fluent chain of moves from this location, see [`NavBuilder`].
+    ///
+    /// # Returns
+    ///
+    /// A `NavBuilder` positioned at this location.
+    pub fn nav(self) -> NavBuilder<T> {
+        NavBuilder {
+            location: Some(self),
+        }
+    }
+
+    /// Walks the enclosing sections from the cursor up to the root, see [`Ancestors`].
+    ///
+    /// # Returns
+    ///
+    /// An `Ancestors` iterator, yielding nothing at the top.
+    pub fn ancestors(&self) -> Ancestors<'_, T> {
+        Ancestors {
+            cursor: &self.cursor,
+            below: None,
+            path: &self.path,
+        }
+    }
+
+    /// Swaps the cursor with its right sibling.
+    ///
+    /// The cursor stays focused on the same node, which is now one position
+    /// further to the right.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a right sibling.
+    /// * `None` - If there is no right sibling or the location is at the top.
+    pub fn swap_right(self) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+        let (first, rest) = right.split_first()?;
+
+        Some(Self {
+            path: Path::Node {
+                left: [first.clone()].into_iter().chain(left.clone()).collect(),
+                right: rest.to_vec(),
+                path: path.clone(),
+            }
+            .into(),
+            cursor: self.cursor,
+        })
+    }
+
+    /// Swaps the cursor with its left sibling.
+    ///
+    /// The cursor stays focused on the same node, which is now one position
+    /// further to the left.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a left sibling.
+    /// * `None` - If there is no left sibling or the location is at the top.
+    pub fn swap_left(self) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+        let (first, rest) = left.split_first()?;
+
+        Some(Self {
+            path: Path::Node {
+                left: rest.to_vec(),
+                right: [first.clone()].into_iter().chain(right.clone()).collect(),
+                path: path.clone(),
+            }
+            .into(),
+            cursor: self.cursor,
+        })
+    }
+
+    /// Counts the children of the parent section, the cursor included.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The number of siblings, the cursor included.
+    /// * `None` - If the location is at the top.
+    pub fn sibling_count(&self) -> Option<usize> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, right, .. } => Some(left.len() + 1 + right.len()),
+        }
+    }
+
+    /// Lists the children of the parent section in order, the cursor included.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<&Tree>)` - The siblings from left to right.
+    /// * `None` - If the location is at the top.
+    pub fn siblings(&self) -> Option<Vec<&Tree<T>>> {
+        match self.path.as_ref() {
+            Path::Top => None,
+            Path::Node { left, right, .. } => Some(
+                left.iter()
+                    .rev()
+                    .chain([&self.cursor])
+                    .chain(right.iter())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Creates a location for every direct child of the current node.
+    ///
+    /// # Returns
+    ///
+    /// The locations of the children from left to right, each with its
+    /// siblings split around it and this location as parent. Empty for an
+    /// item or an empty section.
+    pub fn children(self) -> Vec<Location<T>> {
+        let Tree::Section(children) = &self.cursor else {
+            return vec![];
+        };
+
+        children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| Location {
+                cursor: child.clone(),
+                path: Path::Node {
+                    left: children[..index].iter().rev().cloned().collect(),
+                    right: children[index + 1..].to_vec(),
+                    path: self.path.clone(),
+                }
+                .into(),
+            })
+            .collect()
+    }
+
+    /// Moves the cursor to the next node of the whole tree in preorder.
+    ///
+    /// The cursor enters the first child of a non-empty section, otherwise it
+    /// moves to the right sibling of the closest node on the way up that has one.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the next node.
+    /// * `None` - If the cursor is on the last node in preorder.
+    pub fn go_next(self) -> Option<Self> {
+        if matches!(&self.cursor, Tree::Section(children) if !children.is_empty()) {
+            return self.go_down();
+        }
+
+        let mut location = self;
+        loop {
+            if matches!(location.path.as_ref(), Path::Node { right, .. } if !right.is_empty()) {
+                return location.go_right();
+            }
+
+            location = location.go_up()?;
+        }
+    }
+
+    /// Moves the cursor to the previous node of the whole tree in preorder,
+    /// undoing [`Location::go_next`].
+    ///
+    /// The cursor moves to the last node of the left sibling's subtree, or to
+    /// the parent if there is no left sibling.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the previous node.
+    /// * `None` - If the location is at the top.
+    pub fn go_prev(self) -> Option<Self> {
+        let Some(mut location) = self.clone().go_left() else {
+            return self.go_up();
+        };
+
+        while let Some(child) = location.clone().go_down() {
+            location = child.go_rightmost().expect("a child location has siblings");
+        }
+
+        Some(location)
+    }
+
+    /// Folds the item values of the focused subtree in preorder.
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The initial accumulator.
+    /// * `f` - Combines the accumulator with the next item value.
+    ///
+    /// # Returns
+    ///
+    /// The final accumulator.
+    pub fn fold<A, F: FnMut(A, &T) -> A>(&self, init: A, f: F) -> A {
+        self.iter_items().fold(init, f)
+    }
+
+    /// Inserts several trees to the right of the current node.
+    ///
+    /// The trees keep their iteration order, directly after the cursor, and
+    /// the cursor stays on the current node.
+    ///
+    /// # Arguments
+    ///
+    /// * `trees` - The trees to insert.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the insertion was successful.
+    /// * `None` - If the location is at the top.
+    pub fn insert_right_many(self, trees: impl IntoIterator<Item = Tree<T>>) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+
+        Some(Self {
+            path: Path::Node {
+                left: left.clone(),
+                right: trees.into_iter().chain(right.iter().cloned()).collect(),
+                path: path.clone(),
+            }
+            .into(),
+            cursor: self.cursor,
+        })
+    }
+
+    /// Inserts several trees to the left of the current node.
+    ///
+    /// The trees keep their iteration order, directly before the cursor, and
+    /// the cursor stays on the current node.
+    ///
+    /// # Arguments
+    ///
+    /// * `trees` - The trees to insert.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the insertion was successful.
+    /// * `None` - If the location is at the top.
+    pub fn insert_left_many(self, trees: impl IntoIterator<Item = Tree<T>>) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+
+        let mut inserted = trees.into_iter().collect::<Vec<Tree<T>>>();
+        inserted.reverse();
+
+        Some(Self {
+            path: Path::Node {
+                left: inserted.into_iter().chain(left.iter().cloned()).collect(),
+                right: right.clone(),
+                path: path.clone(),
+            }
+            .into(),
+            cursor: self.cursor,
+        })
+    }
+
+    /// Moves the cursor up by several levels.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of levels to move up.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the ancestor `n` levels up, or this location if `n` is 0.
+    /// * `None` - If the top is reached before moving `n` levels.
+    pub fn up_n(self, n: usize) -> Option<Self> {
+        (0..n).try_fold(self, |location, _| location.go_up())
+    }
+
+    /// Moves the cursor down through first children by several levels.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of levels to move down.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the descendant `n` levels down, or this location if `n` is 0.
+    /// * `None` - If an item or an empty section is reached before moving `n` levels.
+    pub fn down_first_n(self, n: usize) -> Option<Self> {
+        (0..n).try_fold(self, |location, _| location.go_down())
+    }
+
+    /// Returns true if the cursor is an item.
+    pub fn is_leaf(&self) -> bool {
+        matches!(self.cursor, Tree::Item(_))
+    }
+
+    /// Returns true if the cursor is a section, empty or not.
+    pub fn is_section(&self) -> bool {
+        matches!(self.cursor, Tree::Section(_))
+    }
+
+    /// Returns true if the cursor is a section without children.
+    pub fn is_empty_section(&self) -> bool {
+        matches!(&self.cursor, Tree::Section(children) if children.is_empty())
+    }
+
+    /// Returns true if the location is at the top, so it has no parent.
+    pub fn is_top(&self) -> bool {
+        matches!(self.path.as_ref(), Path::Top)
+    }
+
+    /// Moves up to the top, calling a function with every reconstructed ancestor.
+    ///
+    /// Like [`Location::go_to_root`], but after every move up `f` receives
+    /// the section that was just rebuilt, from the nearest parent to the
+    /// root. The cursor itself is not passed to `f`.
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The initial accumulator.
+    /// * `f` - Combines the accumulator with the next reconstructed ancestor.
+    ///
+    /// # Returns
+    ///
+    /// The root of the tree and the final accumulator.
+    pub fn fold_up<A, F: FnMut(A, &Tree<T>) -> A>(self, init: A, mut f: F) -> (Tree<T>, A) {
+        let mut location = self;
+        let mut acc = init;
+
+        while !location.is_top() {
+            location = location.go_up().expect("a node always has a parent");
+            acc = f(acc, &location.cursor);
+        }
+
+        (location.cursor, acc)
+    }
+
+    /// Replaces the children of the current section, keeping the path.
+    ///
+    /// # Arguments
+    ///
+    /// * `children` - The new children of the section.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - With the section's children replaced.
+    /// * `None` - If the current node is an item.
+    pub fn replace_children(mut self, children: Vec<Tree<T>>) -> Option<Self> {
+        match &mut self.cursor {
+            Tree::Item(_) => None,
+            Tree::Section(current) => {
+                *current = children;
+                Some(self)
+            }
+        }
+    }
+
+    /// Moves to the nth child, appending a new last child if it doesn't exist.
+    ///
+    /// Like `HashMap::entry(..).or_insert_with(..)`: `f` is only called when
+    /// the current section has no child at index `n`, and the new child is
+    /// appended at the end, so it ends up at index `n` only if `n` equals the
+    /// number of children.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the child to navigate to.
+    /// * `f` - Builds the child to append when there is none at index `n`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - The location of the existing or the appended child.
+    /// * `None` - If the current node is an item.
+    pub fn nth_or_insert_with<F: FnOnce() -> Tree<T>>(self, n: usize, f: F) -> Option<Self> {
+        let Tree::Section(children) = self.cursor else {
+            return None;
+        };
+
+        if n < children.len() {
+            return Self {
+                cursor: Tree::Section(children),
+                path: self.path,
+            }
+            .get_nth(n);
+        }
+
+        Some(Self {
+            cursor: f(),
+            path: Path::Node {
+                left: children.into_iter().rev().collect(),
+                right: vec![],
+                path: self.path,
+            }
+            .into(),
+        })
+    }
+
+    /// Borrows the sibling context of every level of the path, without cloning.
+    ///
+    /// The slices are taken straight from the `Path::Node`s, so `left` keeps
+    /// the path's order with the nearest sibling first, while `right` is in
+    /// document order.
+    ///
+    /// # Returns
+    ///
+    /// The `(left, right)` siblings at every level, from the cursor's level
+    /// up to the root. A location at the top has no context.
+    pub fn context(&self) -> Vec<Siblings<'_, T>> {
+        let mut context = vec![];
+        let mut path = self.path.as_ref();
+
+        while let Path::Node {
+            left,
+            right,
+            path: parent,
+        } = path
+        {
+            context.push((left.as_slice(), right.as_slice()));
+            path = parent;
+        }
+
+        context
+    }
+
+    /// Cuts the current node out of the tree, see [`Location::delete_returning`].
+    ///
+    /// # Returns
+    ///
+    /// * `Some((Location, Tree))` - The location after the deletion and the cut subtree.
+    /// * `None` - If the location is at the top.
+    pub fn cut(self) -> Option<(Self, Tree<T>)> {
+        self.delete_returning()
+    }
+
+    /// Pastes a tree to the right of the current node and moves the cursor onto it.
+    ///
+    /// Unlike [`Location::insert_right`], the pasted node gets the focus, so
+    /// chained pastes end up in the order they were made.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to paste.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the pasted tree.
+    /// * `None` - If the location is at the top.
+    pub fn paste_right(self, tree: Tree<T>) -> Option<Self> {
+        self.insert_right(tree)?.go_right()
+    }
+
+    /// Pastes a tree to the left of the current node and moves the cursor onto it.
+    ///
+    /// Unlike [`Location::insert_left`], the pasted node gets the focus, so
+    /// chained pastes end up in reverse order of pasting.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to paste.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the pasted tree.
+    /// * `None` - If the location is at the top.
+    pub fn paste_left(self, tree: Tree<T>) -> Option<Self> {
+        self.insert_left(tree)?.go_left()
+    }
+
+    /// Transforms every item value of the whole location, see [`Tree::map`].
+    ///
+    /// The cursor and every sibling stored in the path are converted, so the
+    /// result is at the same position of the converted tree. The path is
+    /// converted from the root level down, then the cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Converts an item value into the new value type.
+    ///
+    /// # Returns
+    ///
+    /// A `Location<U>` at the analogous position.
+    pub fn map<U: Clone, F: FnMut(T) -> U>(self, mut f: F) -> Location<U> {
+        let mut path = Rc::new(Path::Top);
+
+        for (left, right) in self.context().into_iter().rev() {
+            let mut convert = |trees: &[Tree<T>]| {
+                trees
+                    .iter()
+                    .map(|tree| tree.clone().map_with(&mut f))
+                    .collect()
+            };
+
+            path = Path::Node {
+                left: convert(left),
+                right: convert(right),
+                path,
+            }
+            .into();
+        }
+
+        Location {
+            cursor: self.cursor.map_with(&mut f),
+            path,
+        }
+    }
+
+    /// Checks whether two locations describe the same overall tree, wherever their cursors are.
+    ///
+    /// Both trees are rebuilt from borrowed paths with [`Location::ancestors`],
+    /// so neither location is consumed or changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The location to compare with.
+    ///
+    /// # Returns
+    ///
+    /// `true` if reassembling both locations gives equal root trees.
+    pub fn same_tree(&self, other: &Location<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        let root = |location: &Location<T>| {
+            location
+                .ancestors()
+                .last()
+                .unwrap_or_else(|| location.cursor.clone())
+        };
+
+        root(self) == root(other)
+    }
+
+    /// Replaces a section cursor with its children, inlined as siblings.
+    ///
+    /// The children take the place of the section in the parent, in order,
+    /// and the cursor moves to the first of them. This is the inverse of
+    /// [`Location::wrap_right`]. An empty section has no child to focus
+    /// on; use [`Location::delete`] to remove it.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the first spliced child.
+    /// * `None` - If the location is at the top or the cursor is an item or an empty section.
+    pub fn splice(self) -> Option<Self> {
+        let Tree::Section(children) = self.cursor else {
+            return None;
+        };
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+
+        let mut children = children.into_iter();
+        let cursor = children.next()?;
+
+        Some(Self {
+            cursor,
+            path: Path::Node {
+                left: left.clone(),
+                right: children.chain(right.iter().cloned()).collect(),
+                path: path.clone(),
+            }
+            .into(),
+        })
+    }
+
+    /// Groups the cursor and the next `count` right siblings into a new section.
+    ///
+    /// The section takes the place of the cursor and gets the focus. With a
+    /// `count` of zero only the cursor is wrapped. [`Location::splice`]
+    /// undoes the grouping.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of right siblings to group with the cursor.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the new section.
+    /// * `None` - If the location is at the top or there are fewer than `count` right siblings.
+    pub fn wrap_right(self, count: usize) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+        if right.len() < count {
+            return None;
+        }
+
+        let (grouped, rest) = right.split_at(count);
+
+        Some(Self {
+            cursor: Tree::Section([self.cursor].into_iter().chain(grouped.to_vec()).collect()),
+            path: Path::Node {
+                left: left.clone(),
+                right: rest.to_vec(),
+                path: path.clone(),
+            }
+            .into(),
+        })
+    }
+
+    /// Keeps only the children of the current section that match a predicate.
+    ///
+    /// The cursor is the section itself, so it stays on the section with its
+    /// remaining children; no child is focused before or after.
+    ///
+    /// # Arguments
+    ///
+    /// * `pred` - Returns true for the children to keep.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - Focused on the filtered section.
+    /// * `None` - If the current node is an item.
+    pub fn retain_children<F: FnMut(&Tree<T>) -> bool>(mut self, pred: F) -> Option<Self> {
+        match &mut self.cursor {
+            Tree::Item(_) => None,
+            Tree::Section(children) => {
+                children.retain(pred);
+                Some(self)
+            }
+        }
+    }
+
+    /// Describes the position of the cursor as child indices from the root.
+    ///
+    /// Every level contributes the number of left siblings, which is the
+    /// child index taken at that level, so the result can be replayed with
+    /// [`Location::navigate_indices`] from the root location to get back to
+    /// the same cursor. A location at the top has an empty index path.
+    ///
+    /// # Returns
+    ///
+    /// The child index taken at every level, outermost first.
+    pub fn path_indices(&self) -> Vec<usize> {
+        let mut indices = vec![];
+        let mut path = self.path.as_ref();
+
+        while let Path::Node {
+            left, path: parent, ..
+        } = path
+        {
+            indices.push(left.len());
+            path = parent;
+        }
+
+        indices.reverse();
+        indices
+    }
+}
+
+impl<T: Clone> Default for Location<T> {
+    /// Returns `Location::new` of the default tree, an empty section.
+    fn default() -> Self {
+        Location::new(Tree::default())
+    }
+}
+
+#[derive(Debug, Clone)]
+/// An iterator over the locations of a subtree in depth-first preorder.
+///
+/// Every node of the subtree is yielded, sections included, starting with
+/// the node the iterator was created at. Each location carries its full
+/// path, so it can be edited and reassembled like any other. An item or an
+/// empty section yields only itself.
+pub struct PreOrder<T: Clone> {
+    next: Option<(Location<T>, usize)>,
+}
+
+impl<T: Clone> Iterator for PreOrder<T> {
+    type Item = Location<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (location, depth) = self.next.take()?;
+        self.next = location.clone().next_preorder(depth);
+        Some(location)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// An iterator over the nodes of a subtree in breadth-first order.
+///
+/// The node the iterator was created at is yielded first, then its
+/// children from left to right, then its grandchildren, and so on.
+pub struct BreadthFirst<T: Clone> {
+    queue: VecDeque<Tree<T>>,
+}
+
+impl<T: Clone> Iterator for BreadthFirst<T> {
+    type Item = Tree<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.queue.pop_front()?;
+        if let Tree::Section(children) = &tree {
+            self.queue.extend(children.iter().cloned());
+        }
+        Some(tree)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// An iterator over the enclosing sections of a location, created by [`Location::ancestors`].
+///
+/// Every parent section is rebuilt from the path the same way
+/// [`Location::go_up`] does, from the nearest parent up to the root, without
+/// touching the location itself.
+pub struct Ancestors<'a, T: Clone> {
+    cursor: &'a Tree<T>,
+    below: Option<Tree<T>>,
+    path: &'a Path<T>,
+}
+
+impl<T: Clone> Iterator for Ancestors<'_, T> {
+    type Item = Tree<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Path::Node { left, right, path } = self.path else {
+            return None;
+        };
+
+        let below = self.below.take().unwrap_or_else(|| self.cursor.clone());
+        let section = Tree::Section(
+            left.iter()
+                .rev()
+                .cloned()
+                .chain([below])
+                .chain(right.iter().cloned())
+                .collect(),
+        );
+
+        self.below = Some(section.clone());
+        self.path = path;
+        Some(section)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A fluent chain of moves, created by [`Location::nav`].
+///
+/// Every move is applied to the location reached by the previous ones. Once
+/// a move fails, the remaining ones are skipped and `finish` returns `None`.
+pub struct NavBuilder<T: Clone> {
+    location: Option<Location<T>>,
+}
+
+impl<T: Clone> NavBuilder<T> {
+    /// Moves to the first child, see [`Location::go_down`].
+    pub fn down(self) -> Self {
+        self.step(Location::go_down)
+    }
+
+    /// Moves to the parent, see [`Location::go_up`].
+    pub fn up(self) -> Self {
+        self.step(Location::go_up)
+    }
+
+    /// Moves to the left sibling, see [`Location::go_left`].
+    pub fn left(self) -> Self {
+        self.step(Location::go_left)
+    }
+
+    /// Moves to the right sibling, see [`Location::go_right`].
+    pub fn right(self) -> Self {
+        self.step(Location::go_right)
+    }
+
+    /// Moves to the nth child, see [`Location::get_nth`].
+    pub fn nth(self, n: usize) -> Self {
+        Self {
+            location: self.location.and_then(|location| location.get_nth(n)),
+        }
+    }
+
+    /// Finishes the chain.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - The location reached, if every move succeeded.
+    /// * `None` - If any of the moves failed.
+    pub fn finish(self) -> Option<Location<T>> {
+        self.location
+    }
+
+    fn step(self, step: fn(Location<T>) -> Option<Location<T>>) -> Self {
+        Self {
+            location: self.location.and_then(step),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The error returned when an index path does not lead to a node of a tree.
+pub struct InvalidPath {
+    /// The index path that could not be followed.
+    pub indices: Vec<usize>,
+}
+
+impl Display for InvalidPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index path {:?} does not exist in the tree",
+            self.indices
+        )
+    }
+}
+
+impl std::error::Error for InvalidPath {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The error returned when two trees that must have the same shape differ.
+pub struct ShapeMismatch;
+
+impl Display for ShapeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the trees have different shapes")
+    }
+}
+
+impl std::error::Error for ShapeMismatch {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A side of the cursor within its parent section.
+pub enum Direction {
+    /// Towards the first sibling.
+    Left,
+    /// Towards the last sibling.
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The error returned when the cursor cannot be moved.
+pub enum NavError {
+    /// The location is at the top, so it has no parent or siblings.
+    AtTop,
+    /// There is no sibling in the requested direction.
+    NoSibling,
+    /// The current node is an item, so it has no children.
+    NotASection,
+    /// The current node is a section without children.
+    EmptySection,
+}
+
+impl Display for NavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NavError::AtTop => write!(f, "the location is at the top of the tree"),
+            NavError::NoSibling => write!(f, "there is no sibling in that direction"),
+            NavError::NotASection => write!(f, "the current node is not a section"),
+            NavError::EmptySection => write!(f, "the current section is empty"),
+        }
+    }
+}
+
+impl std::error::Error for NavError {}
+
+impl<T: Clone> TryFrom<(Tree<T>, Vec<usize>)> for Location<T> {
+    type Error = InvalidPath;
+
+    /// Creates a location from a root and an index path, as returned by [`Location::rekey`].
+    fn try_from((root, indices): (Tree<T>, Vec<usize>)) -> Result<Self, Self::Error> {
+        Location::from_root_and_indices(root, &indices).ok_or(InvalidPath { indices })
+    }
+}
+
+#[cfg(test)]
 mod test {
 
-    use std::rc::Rc;
+    use std::rc::Rc;
+
+    use crate::{Direction, InvalidPath, Location, NavError, NestedVec, Path, ShapeMismatch, Tree};
+
+    #[test]
+    fn test_new() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location::new(tree.clone());
+
+        assert_eq!(
+            location,
+            Location {
+                cursor: tree.clone(),
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![tree],
+                    path: Rc::new(Path::Top),
+                }
+                .into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_for_readme() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location::new(tree);
+
+        let location = location.go_down().unwrap();
+        assert_eq!(location.cursor, Tree::Item("a"));
+
+        let location = location.go_right().unwrap();
+        assert_eq!(location.cursor, Tree::Item("+"));
+
+        let location = location.go_left().unwrap();
+        assert_eq!(location.cursor, Tree::Item("a"));
+
+        let location = location.insert_right(Tree::Item(".")).unwrap();
+        assert_eq!(
+            location,
+            Location {
+                cursor: Tree::Item("a"),
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![Tree::Item("."), Tree::Item("+"), Tree::Item("b")],
+                    path: Path::Node {
+                        left: vec![],
+                        right: vec![Tree::Section(vec![
+                            Tree::Item("a"),
+                            Tree::Item("+"),
+                            Tree::Item("b")
+                        ])],
+                        path: Path::Top.into()
+                    }
+                    .into()
+                }
+                .into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_go_left_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().go_left(), None);
+    }
+
+    #[test]
+    fn test_go_left() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let result = Location {
+            path: Path::Node {
+                left: vec![Tree::Item("a")],
+                right: vec![Tree::Item("b")],
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![tree.clone()],
+                    path: Path::Top.into(),
+                }
+                .into(),
+            }
+            .into(),
+            cursor: Tree::Item("+"),
+        }
+        .go_left();
+
+        let expect = Some(Location {
+            path: Path::Node {
+                left: vec![],
+                right: vec![Tree::Item("+"), Tree::Item("b")],
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![tree],
+                    path: Path::Top.into(),
+                }
+                .into(),
+            }
+            .into(),
+            cursor: Tree::Item("a"),
+        });
+
+        assert_eq!(result, expect,);
+    }
+
+    #[test]
+    fn test_go_right() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let result = Location {
+            path: Path::Node {
+                left: vec![Tree::Item("a")],
+                right: vec![Tree::Item("b")],
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![tree.clone()],
+                    path: Path::Top.into(),
+                }
+                .into(),
+            }
+            .into(),
+            cursor: Tree::Item("+"),
+        }
+        .go_right();
+
+        let expect = Some(Location {
+            path: Path::Node {
+                right: vec![],
+                left: vec![Tree::Item("+"), Tree::Item("a")],
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![tree],
+                    path: Path::Top.into(),
+                }
+                .into(),
+            }
+            .into(),
+            cursor: Tree::Item("b"),
+        });
+
+        assert_eq!(result, expect);
+    }
+
+    #[test]
+    fn test_go_right_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().go_right(), None);
+    }
+
+    #[test]
+    fn test_go_up_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().go_up(), None);
+    }
+
+    #[test]
+    fn test_go_up() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Node {
+                left: vec![],
+                right: vec![Tree::Item("+"), Tree::Item("b")],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("a"),
+        }
+        .go_up();
+
+        assert_eq!(
+            location,
+            Some(Location {
+                cursor: tree.clone(),
+                path: Path::Top.into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_go_down_none() {
+        let tree = Tree::Item("a");
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.go_down(), None);
+    }
+
+    #[test]
+    fn test_go_down() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(
+            location.go_down(),
+            Some(Location {
+                cursor: Tree::Item("a"),
+                path: Path::Node {
+                    left: [].into(),
+                    right: [Tree::Item("+"), Tree::Item("b")].into(),
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_nth_0() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(
+            location.get_nth(0),
+            Some(Location {
+                cursor: Tree::Item("a"),
+                path: Path::Node {
+                    left: [].into(),
+                    right: [Tree::Item("+"), Tree::Item("b")].into(),
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_nth_1() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(
+            location.get_nth(1),
+            Some(Location {
+                cursor: Tree::Item("+"),
+                path: Path::Node {
+                    left: [Tree::Item("a")].into(),
+                    right: [Tree::Item("b")].into(),
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_nth_2() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(
+            location.get_nth(2),
+            Some(Location {
+                cursor: Tree::Item("b"),
+                path: Path::Node {
+                    left: [Tree::Item("+"), Tree::Item("a")].into(),
+                    right: [].into(),
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_nth_out_of_bounds() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.get_nth(3), None);
+    }
+
+    #[test]
+    fn test_change() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let new_tree = Tree::Item("z");
+
+        assert_eq!(
+            location.change(new_tree.clone()),
+            Location {
+                cursor: new_tree,
+                path: Path::Top.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_change_after_go_left() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let new_tree = Tree::Item("-");
+
+        let updated_location = location
+            .go_down()
+            .and_then(Location::go_right)
+            .map(|loc| loc.change(new_tree.clone()));
+
+        assert_eq!(
+            updated_location,
+            Some(Location {
+                cursor: Tree::Item("-"),
+                path: Path::Node {
+                    left: [Tree::Item("a")].into(),
+                    right: [Tree::Item("b")].into(),
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_insert_left() {
+        let result = Location {
+            path: Path::Node {
+                left: vec![],
+                right: vec![Tree::Item("+"), Tree::Item("b")],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("a"),
+        }
+        .insert_left(Tree::Item("."));
+
+        let expect = Location {
+            path: Path::Node {
+                left: vec![Tree::Item(".")],
+                right: vec![Tree::Item("+"), Tree::Item("b")],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("a"),
+        }
+        .into();
+
+        assert_eq!(result, expect);
+    }
+
+    #[test]
+    fn test_insert_left_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let new_tree = Tree::Item("-");
+
+        assert!(location.insert_left(new_tree).is_none());
+    }
+
+    #[test]
+    fn test_insert_right_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let new_tree = Tree::Item("-");
+
+        assert!(location.insert_right(new_tree).is_none());
+    }
+
+    #[test]
+    fn test_insert_right() {
+        let result = Location {
+            path: Path::Node {
+                left: vec![],
+                right: vec![Tree::Item("+"), Tree::Item("b")],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("a"),
+        }
+        .insert_right(Tree::Item("."));
+
+        let expect = Location {
+            path: Path::Node {
+                left: vec![],
+                right: vec![Tree::Item("."), Tree::Item("+"), Tree::Item("b")],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("a"),
+        }
+        .into();
+
+        assert_eq!(result, expect);
+    }
+
+    #[test]
+    fn test_insert_down() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let new_tree = Tree::Item("-");
+        let updated_location = location.insert_down(new_tree);
+
+        assert_eq!(
+            updated_location,
+            Some(Location {
+                cursor: Tree::Item("-"),
+                path: Path::Node {
+                    left: [].into(),
+                    right: vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")],
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_insert_down_none() {
+        let location = Location {
+            path: Path::Node {
+                left: vec![Tree::Item("a")],
+                right: vec![Tree::Item("b")],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("+"),
+        };
+
+        let new_tree = Tree::Item("-");
+        let updated_location = location.insert_down(new_tree);
+
+        assert_eq!(updated_location, None);
+    }
+
+    #[test]
+    fn test_delete_top() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.delete(), None);
+    }
+
+    #[test]
+    fn test_delete_middle_node() {
+        let location = Location {
+            path: Path::Node {
+                left: vec![Tree::Item("+"), Tree::Item("a")],
+                right: vec![],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("b"),
+        };
+
+        let updated_location = location.delete();
+
+        assert_eq!(
+            updated_location,
+            Some(Location {
+                cursor: Tree::Item("+"),
+                path: Path::Node {
+                    left: [Tree::Item("a")].into(),
+                    right: [].into(),
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_delete_last_node() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let updated_location = location.go_down().and_then(Location::delete);
+
+        assert_eq!(
+            updated_location,
+            Some(Location {
+                cursor: Tree::Item("+"),
+                path: Path::Node {
+                    right: [Tree::Item("b")].into(),
+                    left: [].into(),
+                    path: crate::Path::Top.into(),
+                }
+                .into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_delete_only_child() {
+        let tree = Tree::Section(vec![Tree::Item("a")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let updated_location = location.go_down().and_then(Location::delete);
+
+        assert_eq!(
+            updated_location,
+            Some(Location {
+                cursor: Tree::Section(vec![]),
+                path: crate::Path::Top.into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_navigate() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+            Tree::Item("d"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location.navigate("d r d r").unwrap();
+        assert_eq!(location.cursor, Tree::Item("c"));
+
+        let location = location.navigate("u1r").unwrap();
+        assert_eq!(location.cursor, Tree::Item("d"));
+
+        let location = location.navigate("2l").unwrap();
+        assert_eq!(location.cursor, Tree::Item("a"));
+    }
+
+    #[test]
+    fn test_navigate_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().navigate("d3r"), None);
+        assert_eq!(location.clone().navigate("drdu"), None);
+        assert_eq!(location.clone().navigate("dx"), None);
+        assert_eq!(location.navigate("d2"), None);
+    }
+
+    #[test]
+    fn test_pad_sections_to() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![Tree::Item("b")])]);
+
+        assert_eq!(
+            tree.pad_sections_to(3, "_"),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b"), Tree::Item("_"), Tree::Item("_")]),
+                Tree::Item("_"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_pad_sections_to_keeps_longer_sections() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("+"),
+            Tree::Section(vec![]),
+        ]);
+
+        assert_eq!(
+            tree.pad_sections_to(2, "_"),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Item("+"),
+                Tree::Section(vec![Tree::Item("_"), Tree::Item("_")]),
+            ])
+        );
+        assert_eq!(Tree::Item("a").pad_sections_to(2, "_"), Tree::Item("a"));
+    }
+
+    #[test]
+    fn test_as_item() {
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Item("a"),
+        };
+
+        assert_eq!(location.as_item(), Some(&"a"));
+        assert_eq!(location.as_section(), None);
+    }
+
+    #[test]
+    fn test_as_section() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(
+            location.as_section(),
+            Some([Tree::Item("a"), Tree::Item("+"), Tree::Item("b")].as_slice())
+        );
+        assert_eq!(location.as_item(), None);
+    }
+
+    #[test]
+    fn test_fold_with_path() {
+        let tree = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Item(3)]),
+        ]);
+
+        let result = tree.fold_with_path(
+            |path, value| format!("{value}@{path:?}"),
+            |path, children| format!("{path:?}({})", children.join(" ")),
+        );
+
+        assert_eq!(result, "[](1@[0] [1](2@[1, 0] 3@[1, 1]))");
+    }
+
+    #[test]
+    fn test_move_to() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+            Tree::Section(vec![Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let moved = location.navigate("ddr").unwrap().move_to(&[1], 0).unwrap();
+        assert_eq!(moved.cursor, Tree::Item("b"));
+
+        let (root, indices) = moved.rekey();
+        assert_eq!(indices, vec![1, 0]);
+        assert_eq!(
+            root,
+            Tree::Section(vec![
+                Tree::Section(vec![Tree::Item("a")]),
+                Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_move_to_shifted_target() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+            Tree::Section(vec![Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let moved = location.go_down().unwrap().move_to(&[1], 1).unwrap();
+
+        let (root, indices) = moved.rekey();
+        assert_eq!(indices, vec![0, 1]);
+        assert_eq!(
+            root,
+            Tree::Section(vec![Tree::Section(vec![
+                Tree::Item("c"),
+                Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+            ])])
+        );
+    }
+
+    #[test]
+    fn test_move_to_none() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().move_to(&[], 0), None);
+        assert_eq!(location.clone().go_down().unwrap().move_to(&[0], 0), None);
+        assert_eq!(
+            location.clone().navigate("dd").unwrap().move_to(&[1], 0),
+            None
+        );
+        assert_eq!(location.navigate("dd").unwrap().move_to(&[], 3), None);
+    }
+
+    #[test]
+    fn test_is_subtree_of() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![
+                Tree::Item("b"),
+                Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+            ]),
+        ]);
+
+        let subtree = Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]);
+
+        assert!(subtree.is_subtree_of(&tree));
+        assert!(Tree::Item("b").is_subtree_of(&tree));
+        assert!(tree.is_subtree_of(&tree));
+    }
+
+    #[test]
+    fn test_is_subtree_of_absent() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+        ]);
+
+        assert!(!Tree::Section(vec![Tree::Item("c")]).is_subtree_of(&tree));
+        assert!(!Tree::Item("e").is_subtree_of(&tree));
+        assert!(!tree.is_subtree_of(&Tree::Item("a")));
+    }
+
+    #[test]
+    fn test_distinct_leaf_count() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("+"),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Item("b"),
+                Tree::Section(vec![]),
+            ]),
+            Tree::Item("+"),
+        ]);
+
+        assert_eq!(tree.distinct_leaf_count(), 3);
+        assert_eq!(Tree::<&str>::Section(vec![]).distinct_leaf_count(), 0);
+    }
+
+    #[test]
+    fn test_go_right_wrapping() {
+        let location = Location {
+            path: Path::Node {
+                left: vec![Tree::Item("+"), Tree::Item("a")],
+                right: vec![],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("b"),
+        };
+
+        assert_eq!(
+            location.go_right_wrapping(),
+            Some(Location {
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![Tree::Item("+"), Tree::Item("b")],
+                    path: Path::Top.into(),
+                }
+                .into(),
+                cursor: Tree::Item("a"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_go_left_wrapping() {
+        let location = Location {
+            path: Path::Node {
+                left: vec![],
+                right: vec![Tree::Item("+"), Tree::Item("b")],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Item("a"),
+        };
+
+        assert_eq!(
+            location.go_left_wrapping(),
+            Some(Location {
+                path: Path::Node {
+                    left: vec![Tree::Item("+"), Tree::Item("a")],
+                    right: vec![],
+                    path: Path::Top.into(),
+                }
+                .into(),
+                cursor: Tree::Item("b"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_go_wrapping_without_wrap() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().go_right_wrapping(), None);
+        assert_eq!(location.clone().go_left_wrapping(), None);
+
+        let location = location.go_down().unwrap();
+        assert_eq!(
+            location.clone().go_right_wrapping(),
+            location.clone().go_right()
+        );
+
+        let location = location.go_right().unwrap();
+        assert_eq!(location.clone().go_left_wrapping(), location.go_left());
+    }
+
+    #[test]
+    fn test_go_wrapping_only_child() {
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Section(vec![Tree::Item("a")]),
+        }
+        .go_down()
+        .unwrap();
+
+        assert_eq!(location.clone().go_right_wrapping(), Some(location.clone()));
+        assert_eq!(location.clone().go_left_wrapping(), Some(location));
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut inner = Vec::with_capacity(16);
+        inner.push(Tree::Item("b"));
+
+        let mut outer = Vec::with_capacity(16);
+        outer.push(Tree::Item("a"));
+        outer.push(Tree::Section(inner));
+
+        let mut tree = Tree::Section(outer);
+        tree.shrink_to_fit();
+
+        let Tree::Section(children) = &tree else {
+            panic!("expected a section");
+        };
+        assert!(children.capacity() < 16);
+        assert!(children.capacity() >= children.len());
+
+        let Tree::Section(grandchildren) = &children[1] else {
+            panic!("expected a section");
+        };
+        assert!(grandchildren.capacity() < 16);
+        assert!(grandchildren.capacity() >= grandchildren.len());
+    }
+
+    #[test]
+    fn test_preorder_locations() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        };
+
+        let cursors = location
+            .preorder_locations()
+            .map(|location| location.cursor)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            cursors,
+            vec![
+                tree,
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b")]),
+                Tree::Item("b"),
+                Tree::Item("c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preorder_locations_stays_in_subtree() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let cursors = location
+            .navigate("dr")
+            .unwrap()
+            .preorder_locations()
+            .map(|location| location.cursor)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            cursors,
+            vec![Tree::Section(vec![Tree::Item("b")]), Tree::Item("b")]
+        );
+    }
+
+    #[test]
+    fn test_preorder_locations_editable() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location
+            .preorder_locations()
+            .nth(3)
+            .unwrap()
+            .change(Tree::Item("z"));
+
+        let (root, indices) = location.rekey();
+        assert_eq!(indices, vec![1, 0]);
+        assert_eq!(
+            root,
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("z")]),
+                Tree::Item("c"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_zip_apply() {
+        let functions: Tree<fn(&i32) -> i32> = Tree::Section(vec![
+            Tree::Item(|value| value + 1),
+            Tree::Section(vec![
+                Tree::Item(|value| value * 2),
+                Tree::Item(|value| -value),
+            ]),
+        ]);
+
+        let values = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Item(3)]),
+        ]);
+
+        assert_eq!(
+            functions.zip_apply(&values),
+            Some(Tree::Section(vec![
+                Tree::Item(2),
+                Tree::Section(vec![Tree::Item(4), Tree::Item(-3)]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_zip_apply_mismatch() {
+        let functions: Tree<fn(&i32) -> i32> = Tree::Section(vec![
+            Tree::Item(|value| value + 1),
+            Tree::Item(|value| value * 2),
+        ]);
+
+        assert_eq!(
+            functions.zip_apply(&Tree::Section(vec![Tree::Item(1)])),
+            None
+        );
+        assert_eq!(
+            functions.zip_apply(&Tree::Section(vec![
+                Tree::Item(1),
+                Tree::Section(vec![Tree::Item(2)]),
+            ])),
+            None
+        );
+        assert_eq!(functions.zip_apply(&Tree::Item(1)), None);
+    }
+
+    #[test]
+    fn test_rekey() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        };
+
+        assert_eq!(location.clone().rekey(), (tree.clone(), vec![]));
+        assert_eq!(
+            location.navigate("drdr").unwrap().rekey(),
+            (tree, vec![1, 1])
+        );
+    }
+
+    #[test]
+    fn test_from_root_and_indices() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        };
+
+        assert_eq!(
+            Location::from_root_and_indices(tree.clone(), &[]),
+            Some(location.clone())
+        );
+        assert_eq!(
+            Location::from_root_and_indices(tree.clone(), &[1, 1]),
+            location.navigate("drdr")
+        );
+        assert_eq!(Location::from_root_and_indices(tree.clone(), &[2]), None);
+        assert_eq!(Location::from_root_and_indices(tree, &[0, 0]), None);
+    }
+
+    #[test]
+    fn test_rekey_round_trip() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location::rooted(tree.clone())
+            .go_down()
+            .unwrap()
+            .go_right()
+            .unwrap();
+
+        let (root, indices) = location.clone().rekey();
+        assert_eq!(root, tree);
+        assert_eq!(indices, vec![1]);
+
+        let restored = Location::from_root_and_indices(root, &indices).unwrap();
+
+        assert_eq!(restored, location);
+        assert_eq!(restored.go_right(), location.go_right());
+    }
+
+    #[test]
+    fn test_split_children() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("b"),
+            Tree::Item(","),
+            Tree::Item("c"),
+        ]);
+
+        assert_eq!(
+            tree.split_children(|child| child == &Tree::Item(",")),
+            Tree::Section(vec![
+                Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+                Tree::Section(vec![Tree::Item("c")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_split_children_empty_groups() {
+        let tree = Tree::Section(vec![
+            Tree::Item(","),
+            Tree::Item("a"),
+            Tree::Item(","),
+            Tree::Item(","),
+        ]);
+
+        assert_eq!(
+            tree.split_children(|child| child == &Tree::Item(",")),
+            Tree::Section(vec![
+                Tree::Section(vec![]),
+                Tree::Section(vec![Tree::Item("a")]),
+                Tree::Section(vec![]),
+                Tree::Section(vec![]),
+            ])
+        );
+        assert_eq!(
+            Tree::Item(",").split_children(|child| child == &Tree::Item(",")),
+            Tree::Item(",")
+        );
+    }
+
+    #[test]
+    fn test_update_from() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+            Tree::Item("d"),
+        ]);
+
+        let overlay = Tree::Section(vec![Tree::Item("x"), Tree::Section(vec![Tree::Item("y")])]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location.update_from(&overlay, |tree, other| match (tree, other) {
+            (Tree::Item(_), Some(Tree::Item(value))) => Tree::Item(value),
+            _ => tree.clone(),
+        });
+
+        assert_eq!(
+            location,
+            Location {
+                path: Path::Top.into(),
+                cursor: Tree::Section(vec![
+                    Tree::Item("x"),
+                    Tree::Section(vec![Tree::Item("y"), Tree::Item("c")]),
+                    Tree::Item("d"),
+                ]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_from_visits_every_node() {
+        let tree = Tree::Section(vec![Tree::Item(1), Tree::Section(vec![Tree::Item(2)])]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location.update_from(&Tree::Item(0), |tree, other| match tree {
+            Tree::Item(value) => Tree::Item(value * 10),
+            Tree::Section(children) if other.is_none() => {
+                Tree::Section([children.clone(), vec![Tree::Item(0)]].concat())
+            }
+            Tree::Section(_) => tree.clone(),
+        });
+
+        assert_eq!(
+            location.cursor,
+            Tree::Section(vec![
+                Tree::Item(10),
+                Tree::Section(vec![Tree::Item(20), Tree::Item(0)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_ancestors_of() {
+        let inner = Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]);
+        let middle = Tree::Section(vec![Tree::Item("a"), inner.clone()]);
+        let tree = Tree::Section(vec![middle.clone(), Tree::Item("d")]);
+
+        assert_eq!(tree.ancestors_of(&[0, 1, 0]), vec![&tree, &middle, &inner]);
+        assert_eq!(tree.ancestors_of(&[1]), vec![&tree]);
+        assert!(tree.ancestors_of(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_ancestors_of_invalid_path() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+
+        assert!(tree.ancestors_of(&[2]).is_empty());
+        assert!(tree.ancestors_of(&[0, 0]).is_empty());
+    }
+
+    #[test]
+    fn test_delete_at() {
+        let location = Location {
+            path: Path::Node {
+                left: vec![Tree::Item("a")],
+                right: vec![],
+                path: Path::Top.into(),
+            }
+            .into(),
+            cursor: Tree::Section(vec![
+                Tree::Item("b"),
+                Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+            ]),
+        };
+
+        assert_eq!(
+            location.delete_at(&[1, 0]),
+            Some(Location {
+                path: Path::Node {
+                    left: vec![Tree::Item("a")],
+                    right: vec![],
+                    path: Path::Top.into(),
+                }
+                .into(),
+                cursor: Tree::Section(vec![Tree::Item("b"), Tree::Section(vec![Tree::Item("d")]),]),
+            })
+        );
+    }
+
+    #[test]
+    fn test_delete_at_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().delete_at(&[]), None);
+        assert_eq!(location.clone().delete_at(&[3]), None);
+        assert_eq!(location.delete_at(&[0, 0]), None);
+    }
+
+    #[test]
+    fn test_map_accum() {
+        let tree = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Item(3)]),
+            Tree::Item(4),
+        ]);
+
+        let (total, sums) = tree.map_accum(0, |sum, value| (sum + value, sum + value));
+
+        assert_eq!(total, 10);
+        assert_eq!(
+            sums,
+            Tree::Section(vec![
+                Tree::Item(1),
+                Tree::Section(vec![Tree::Item(3), Tree::Item(6)]),
+                Tree::Item(10),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cleanup_after_delete() {
+        let tree = Tree::Section(vec![Tree::Section(vec![Tree::Item("a")]), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location.navigate("dd").and_then(Location::delete).unwrap();
+        assert_eq!(location.cursor, Tree::Section(vec![]));
+
+        assert_eq!(
+            location.cleanup(),
+            Location {
+                cursor: Tree::Item("b"),
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![],
+                    path: Path::Top.into(),
+                }
+                .into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_cleanup_only_child() {
+        let tree = Tree::Section(vec![Tree::Section(vec![Tree::Item("a")])]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location
+            .navigate("dd")
+            .and_then(Location::delete)
+            .unwrap()
+            .cleanup();
+
+        assert_eq!(
+            location,
+            Location {
+                cursor: Tree::Section(vec![]),
+                path: Path::Top.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_cleanup_unchanged() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![])]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().cleanup(), location);
+
+        let location = location.go_down().unwrap();
+        assert_eq!(location.clone().cleanup(), location);
+
+        let empty = Location {
+            path: Path::Top.into(),
+            cursor: Tree::<&str>::Section(vec![]),
+        };
+        assert_eq!(empty.clone().cleanup(), empty);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("\"c\"")]),
+        ]);
+
+        let dot = tree.to_dot();
+
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(
+            dot.lines().filter(|line| line.contains("[label=")).count(),
+            5
+        );
+        assert_eq!(dot.lines().filter(|line| line.contains("->")).count(), 4);
+        assert!(dot.contains("    n0 [label=\"[]\", shape=box];\n"));
+        assert!(dot.contains("    n2 [label=\"[1]\", shape=box];\n"));
+        assert!(dot.contains("    n4 [label=\"\\\"c\\\"\"];\n"));
+        assert!(dot.contains("    n2 -> n4;\n"));
+    }
+
+    #[test]
+    fn test_map_ancestor() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location
+            .navigate("drdr")
+            .unwrap()
+            .map_ancestor(2, |tree| match tree {
+                Tree::Section(children) => {
+                    Tree::Section([children, vec![Tree::Item("z")]].concat())
+                }
+                item => item,
+            })
+            .unwrap();
+
+        assert_eq!(location.cursor, Tree::Item("c"));
+        assert_eq!(
+            location.rekey(),
+            (
+                Tree::Section(vec![
+                    Tree::Item("a"),
+                    Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+                    Tree::Item("z"),
+                ]),
+                vec![1, 1]
+            )
+        );
+    }
+
+    #[test]
+    fn test_map_ancestor_none() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let location = location.go_down().unwrap().go_right().unwrap();
+
+        assert_eq!(location.clone().map_ancestor(2, |tree| tree), None);
+        assert_eq!(location.clone().map_ancestor(1, |_| Tree::Item("x")), None);
+        assert_eq!(
+            location
+                .map_ancestor(0, |_| Tree::Item("-"))
+                .map(|location| location.cursor),
+            Some(Tree::Item("-"))
+        );
+    }
+
+    #[test]
+    fn test_iter_with_neighbors() {
+        let a = Tree::Item("a");
+        let b = Tree::Item("b");
+        let c = Tree::Item("c");
+        let d = Tree::Item("d");
+        let section = Tree::Section(vec![b.clone(), c.clone()]);
+        let tree = Tree::Section(vec![a.clone(), section.clone(), d.clone()]);
+
+        assert_eq!(
+            tree.iter_with_neighbors().collect::<Vec<_>>(),
+            vec![
+                (None, &tree, None),
+                (None, &a, Some(&section)),
+                (Some(&a), &section, Some(&d)),
+                (None, &b, Some(&c)),
+                (Some(&b), &c, None),
+                (Some(&section), &d, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_from() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location::try_from((tree.clone(), vec![1, 0]));
+
+        assert_eq!(
+            location,
+            Ok(Location {
+                cursor: Tree::Item("b"),
+                path: Path::Node {
+                    left: vec![],
+                    right: vec![Tree::Item("c")],
+                    path: Path::Node {
+                        left: vec![Tree::Item("a")],
+                        right: vec![],
+                        path: Path::Top.into(),
+                    }
+                    .into(),
+                }
+                .into(),
+            })
+        );
+        assert_eq!(location.unwrap().rekey(), (tree, vec![1, 0]));
+    }
+
+    #[test]
+    fn test_try_from_invalid() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let error = Location::try_from((tree, vec![0, 1])).unwrap_err();
+
+        assert_eq!(
+            error,
+            InvalidPath {
+                indices: vec![0, 1]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "index path [0, 1] does not exist in the tree"
+        );
+    }
+
+    #[test]
+    fn test_imbalance_balanced() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+            Tree::Section(vec![Tree::Item("c")]),
+        ]);
+
+        assert_eq!(tree.imbalance(), 0);
+        assert_eq!(Tree::Item("a").imbalance(), 0);
+        assert_eq!(Tree::<&str>::Section(vec![]).imbalance(), 0);
+    }
+
+    #[test]
+    fn test_imbalance_skewed() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Section(vec![Tree::Section(vec![Tree::Item(
+                "b",
+            )])])]),
+        ]);
+
+        assert_eq!(tree.imbalance(), 3);
+
+        let nested = Tree::Section(vec![Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Section(vec![Tree::Item("b")])]),
+        ])]);
+
+        assert_eq!(nested.imbalance(), 2);
+    }
+
+    #[test]
+    fn test_merge_sorted_children() {
+        let left = Tree::Section(vec![Tree::Item(1), Tree::Item(4), Tree::Item(6)]);
+        let right = Tree::Section(vec![
+            Tree::Item(2),
+            Tree::Item(4),
+            Tree::Item(8),
+            Tree::Item(9),
+        ]);
+
+        assert_eq!(
+            left.merge_sorted_children(right),
+            Some(Tree::Section(vec![
+                Tree::Item(1),
+                Tree::Item(2),
+                Tree::Item(4),
+                Tree::Item(4),
+                Tree::Item(6),
+                Tree::Item(8),
+                Tree::Item(9),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_merge_sorted_children_rejected() {
+        let sorted = Tree::Section(vec![Tree::Item(1), Tree::Item(2)]);
+        let mixed = Tree::Section(vec![Tree::Item(1), Tree::Section(vec![Tree::Item(2)])]);
+
+        assert_eq!(sorted.clone().merge_sorted_children(mixed), None);
+        assert_eq!(sorted.clone().merge_sorted_children(Tree::Item(3)), None);
+        assert_eq!(Tree::Item(3).merge_sorted_children(sorted), None);
+    }
+
+    #[test]
+    fn test_change_checked() {
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+        };
+
+        let tree = Tree::Section(vec![Tree::Item("x"), Tree::Item("y"), Tree::Item("z")]);
+        assert_eq!(
+            location.clone().change_checked(tree.clone()),
+            Ok(location.change(tree))
+        );
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Item("a"),
+        };
+        assert_eq!(
+            location.clone().change_checked(Tree::Item("b")),
+            Ok(location.change(Tree::Item("b")))
+        );
+    }
+
+    #[test]
+    fn test_change_checked_rejected() {
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+        };
+
+        assert_eq!(
+            location.clone().change_checked(Tree::Item("x")),
+            Err(Tree::Item("x"))
+        );
+        assert_eq!(
+            location.change_checked(Tree::Section(vec![Tree::Item("x")])),
+            Err(Tree::Section(vec![Tree::Item("x")]))
+        );
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Item("a"),
+        };
+        assert_eq!(
+            location.change_checked(Tree::Section(vec![])),
+            Err(Tree::Section(vec![]))
+        );
+    }
+
+    #[test]
+    fn test_leaves_rev() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![
+                Tree::Item("b"),
+                Tree::Section(vec![]),
+                Tree::Item("c"),
+            ]),
+            Tree::Item("d"),
+        ]);
+
+        assert_eq!(
+            tree.leaves_rev().collect::<Vec<_>>(),
+            vec![&"d", &"c", &"b", &"a"]
+        );
+        assert_eq!(Tree::<&str>::Section(vec![]).leaves_rev().next(), None);
+    }
+
+    #[test]
+    fn test_peek() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.peek_left(), None);
+        assert_eq!(location.peek_right(), None);
+        assert_eq!(
+            location.peek_down(),
+            location
+                .clone()
+                .go_down()
+                .map(|location| location.cursor)
+                .as_ref()
+        );
+
+        let location = location.navigate("dr").unwrap();
+        assert_eq!(
+            location.peek_left(),
+            location
+                .clone()
+                .go_left()
+                .map(|location| location.cursor)
+                .as_ref()
+        );
+        assert_eq!(
+            location.peek_right(),
+            location
+                .clone()
+                .go_right()
+                .map(|location| location.cursor)
+                .as_ref()
+        );
+        assert_eq!(location.peek_down(), Some(&Tree::Item("b")));
+
+        let location = location.go_right().unwrap();
+        assert_eq!(location.peek_right(), None);
+        assert_eq!(location.peek_down(), None);
+    }
+
+    #[test]
+    fn test_unwrap_single_item_sections() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a")]),
+            Tree::Section(vec![Tree::Section(vec![Tree::Item("b")])]),
+            Tree::Section(vec![Tree::Item("c"), Tree::Section(vec![Tree::Item("d")])]),
+            Tree::Section(vec![]),
+        ]);
+
+        assert_eq!(
+            tree.unwrap_single_item_sections(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Item("b"),
+                Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+                Tree::Section(vec![]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_unwrap_single_item_sections_root() {
+        let tree = Tree::Section(vec![Tree::Section(vec![Tree::Item("a")])]);
+
+        assert_eq!(tree.unwrap_single_item_sections(), Tree::Item("a"));
+    }
+
+    #[test]
+    fn test_child_index() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let index = location.child_index(|child| child == &Tree::Item("+"));
+        assert_eq!(index, Some(1));
+        assert_eq!(
+            location
+                .get_nth(index.unwrap())
+                .map(|location| location.cursor),
+            Some(Tree::Item("+"))
+        );
+    }
+
+    #[test]
+    fn test_child_index_none() {
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Item("a"),
+        };
+
+        assert_eq!(location.child_index(|_| true), None);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Section(vec![Tree::Item("a")]),
+        };
+
+        assert_eq!(
+            location.child_index(|child| child == &Tree::Item("b")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_go_to_root() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![
+                Tree::Item("b"),
+                Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+                Tree::Item("e"),
+            ]),
+            Tree::Item("f"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        };
+
+        let deep = location.clone().navigate("drdrdr").unwrap();
+        assert_eq!(deep.cursor, Tree::Item("d"));
+        assert_eq!(deep.clone().go_to_root(), tree);
+        assert_eq!(location.clone().go_to_root(), tree);
+
+        let edited = deep.change(Tree::Item("z")).go_to_root();
+        assert_eq!(
+            edited,
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![
+                    Tree::Item("b"),
+                    Tree::Section(vec![Tree::Item("c"), Tree::Item("z")]),
+                    Tree::Item("e"),
+                ]),
+                Tree::Item("f"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_root_location() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let root = location.clone().navigate("drd").unwrap().root_location();
+        assert_eq!(root, location);
+        assert_eq!(root.clone().root_location(), root);
+    }
+
+    #[test]
+    fn test_to_nested_vec() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Section(vec![])]),
+        ]);
+
+        let nested = tree.to_nested_vec();
+
+        assert_eq!(
+            nested,
+            NestedVec::Branch(vec![
+                NestedVec::Leaf("a"),
+                NestedVec::Branch(vec![NestedVec::Leaf("b"), NestedVec::Branch(vec![])]),
+            ])
+        );
+        assert_eq!(Tree::from_nested_vec(nested), tree);
+    }
+
+    #[test]
+    fn test_from_nested_vec() {
+        let nested = NestedVec::Branch(vec![
+            NestedVec::Branch(vec![NestedVec::Leaf(1)]),
+            NestedVec::Leaf(2),
+        ]);
+
+        let tree = Tree::from_nested_vec(nested.clone());
+
+        assert_eq!(
+            tree,
+            Tree::Section(vec![Tree::Section(vec![Tree::Item(1)]), Tree::Item(2)])
+        );
+        assert_eq!(tree.to_nested_vec(), nested);
+        assert_eq!(Tree::from_nested_vec(NestedVec::Leaf(3)), Tree::Item(3));
+    }
+
+    #[test]
+    fn test_iter_items() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![
+                Tree::Section(vec![Tree::Item("b"), Tree::Section(vec![])]),
+                Tree::Item("c"),
+            ]),
+            Tree::Section(vec![]),
+            Tree::Item("d"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(
+            location.iter_items().collect::<Vec<_>>(),
+            vec![&"a", &"b", &"c", &"d"]
+        );
+        assert_eq!(location.count_items(), 4);
+
+        let location = location.navigate("dr").unwrap();
+        assert_eq!(location.iter_items().collect::<Vec<_>>(), vec![&"b", &"c"]);
+        assert_eq!(location.count_items(), 2);
+    }
+
+    #[test]
+    fn test_iter_items_empty() {
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::<&str>::Section(vec![Tree::Section(vec![])]),
+        };
+
+        assert_eq!(location.iter_items().next(), None);
+        assert_eq!(location.count_items(), 0);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Item("a"),
+        };
+
+        assert_eq!(location.iter_items().collect::<Vec<_>>(), vec![&"a"]);
+    }
+
+    #[test]
+    fn test_select_to() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+            Tree::Item("d"),
+            Tree::Section(vec![Tree::Item("e"), Tree::Item("f")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let start = location.clone().navigate("drdr").unwrap();
+        let end = location.navigate("d3rd").unwrap();
+
+        let expected = vec![Tree::Item("c"), Tree::Item("d"), Tree::Item("e")];
+
+        assert_eq!(start.select_to(&end), expected);
+        assert_eq!(end.select_to(&start), expected);
+    }
+
+    #[test]
+    fn test_select_to_whole_sections() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+            Tree::Section(vec![Tree::Item("d")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let start = location.clone().go_down().unwrap();
+        let end = location.clone().navigate("d2r").unwrap();
+
+        assert_eq!(
+            start.select_to(&end),
+            vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+                Tree::Section(vec![Tree::Item("d")]),
+            ]
+        );
+
+        let section = location.clone().navigate("dr").unwrap();
+        let inner = location.navigate("drdr").unwrap();
+
+        assert_eq!(
+            inner.select_to(&section),
+            vec![Tree::Section(vec![Tree::Item("b"), Tree::Item("c")])]
+        );
+        assert_eq!(inner.select_to(&inner), vec![Tree::Item("c")]);
+    }
+
+    #[test]
+    fn test_depth() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        assert_eq!(Location::new(tree.clone()).depth(), 1);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+        assert_eq!(location.depth(), 0);
+
+        let location = location.navigate("drd").unwrap();
+        assert_eq!(location.cursor, Tree::Item("b"));
+        assert_eq!(location.depth(), 2);
+        assert_eq!(location.go_up().unwrap().depth(), 1);
+    }
+
+    #[test]
+    fn test_index() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+        assert_eq!(location.index(), None);
+
+        let location = location.get_nth(2).unwrap();
+        assert_eq!(location.index(), Some(2));
+        assert_eq!(location.clone().go_left().unwrap().index(), Some(1));
+
+        let n = location.index().unwrap();
+        let round_trip = location.clone().go_up().unwrap().get_nth(n).unwrap();
+        assert_eq!(round_trip, location);
+    }
+
+    #[test]
+    fn test_try_navigation() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![])]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().try_go_up(), Err(NavError::AtTop));
+        assert_eq!(location.clone().try_go_left(), Err(NavError::AtTop));
+        assert_eq!(location.clone().try_go_right(), Err(NavError::AtTop));
+
+        let first = location.try_go_down().unwrap();
+        assert_eq!(first.cursor, Tree::Item("a"));
+        assert_eq!(first.clone().try_go_left(), Err(NavError::NoSibling));
+        assert_eq!(first.clone().try_go_down(), Err(NavError::NotASection));
+
+        let empty = first.try_go_right().unwrap();
+        assert_eq!(empty.clone().try_go_right(), Err(NavError::NoSibling));
+        assert_eq!(empty.clone().try_go_down(), Err(NavError::EmptySection));
+        assert_eq!(
+            empty.try_go_up().unwrap().cursor,
+            Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![])])
+        );
+
+        assert_eq!(
+            NavError::EmptySection.to_string(),
+            "the current section is empty"
+        );
+    }
+
+    #[test]
+    fn test_rewrite() {
+        fn rule(tree: &Tree<&'static str>) -> Option<Tree<&'static str>> {
+            let Tree::Section(children) = tree else {
+                return None;
+            };
+
+            match children.as_slice() {
+                [Tree::Item("+"), x, Tree::Item("0")] | [Tree::Item("*"), x, Tree::Item("1")] => {
+                    Some(x.clone())
+                }
+                [Tree::Item("*"), _, Tree::Item("0")] => Some(Tree::Item("0")),
+                _ => None,
+            }
+        }
+
+        // (+ (* a (* b 0)) (* 0 1)) => (+ (* a 0) 0) => (+ 0 0) => 0
+        let tree = Tree::Section(vec![
+            Tree::Item("+"),
+            Tree::Section(vec![
+                Tree::Item("*"),
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("*"), Tree::Item("b"), Tree::Item("0")]),
+            ]),
+            Tree::Section(vec![Tree::Item("*"), Tree::Item("0"), Tree::Item("1")]),
+        ]);
+
+        assert_eq!(tree.rewrite(rule), Tree::Item("0"));
+
+        let tree = Tree::Section(vec![Tree::Item("-"), Tree::Item("a"), Tree::Item("1")]);
+        assert_eq!(tree.clone().rewrite(rule), tree);
+    }
+
+    #[test]
+    fn test_rewrite_limit() {
+        let tree = Tree::Item(0).rewrite(|tree| match tree {
+            Tree::Item(n) => Some(Tree::Item(n + 1)),
+            Tree::Section(_) => None,
+        });
+
+        assert_eq!(tree, Tree::Item(Tree::<usize>::REWRITE_LIMIT));
+    }
+
+    #[test]
+    fn test_preorder() {
+        let item = Location {
+            path: Path::Top.into(),
+            cursor: Tree::Item("a"),
+        };
+        assert_eq!(item.clone().preorder().collect::<Vec<_>>(), vec![item]);
+
+        let empty = Location {
+            path: Path::Top.into(),
+            cursor: Tree::<&str>::Section(vec![]),
+        };
+        assert_eq!(empty.clone().preorder().collect::<Vec<_>>(), vec![empty]);
+
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![])]),
+            Tree::Item("b"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let preorder = location.clone().preorder().collect::<Vec<_>>();
+        assert_eq!(preorder.len(), 5);
+        assert_eq!(preorder[2], location.clone().navigate("dd").unwrap());
+        assert_eq!(preorder[3], location.clone().navigate("ddr").unwrap());
+        assert_eq!(preorder[4], location.navigate("dr").unwrap());
+    }
+
+    #[test]
+    fn test_make_section() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let item = location
+            .clone()
+            .navigate("dr")
+            .unwrap()
+            .make_section()
+            .insert_right(Tree::Item("c"))
+            .unwrap();
+
+        assert_eq!(item.cursor, Tree::Item("b"));
+        assert_eq!(
+            item.go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+            ])
+        );
+
+        assert_eq!(location.clone().make_section(), location);
+    }
+
+    #[test]
+    fn test_go_leftmost_and_rightmost() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("b"),
+            Tree::Item("c"),
+            Tree::Item("d"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+        assert_eq!(location.clone().go_leftmost(), None);
+        assert_eq!(location.clone().go_rightmost(), None);
+
+        let middle = location.clone().get_nth(1).unwrap();
+
+        let leftmost = middle.clone().go_leftmost().unwrap();
+        assert_eq!(leftmost, location.clone().get_nth(0).unwrap());
+        assert_eq!(leftmost.clone().go_leftmost(), Some(leftmost));
+
+        let rightmost = middle.go_rightmost().unwrap();
+        assert_eq!(rightmost, location.get_nth(3).unwrap());
+        assert_eq!(rightmost.clone().go_rightmost(), Some(rightmost));
+    }
+
+    #[test]
+    fn test_depth_histogram() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![
+                Tree::Item("b"),
+                Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+            ]),
+            Tree::Section(vec![]),
+        ]);
+
+        assert_eq!(tree.depth_histogram(), vec![1, 3, 2, 2]);
+        let deepest = 3;
+        assert_eq!(tree.depth_histogram().len(), deepest + 1);
+        assert_eq!(Tree::Item("a").depth_histogram(), vec![1]);
+        assert_eq!(Tree::<&str>::Section(vec![]).depth_histogram(), vec![1]);
+    }
+
+    #[test]
+    fn test_map() {
+        let tree = Tree::Section(vec![
+            Tree::Item("1"),
+            Tree::Section(vec![Tree::Item("2"), Tree::Section(vec![])]),
+            Tree::Item("3"),
+        ]);
+
+        let mut visited = vec![];
+        let mapped = tree.map(|value| {
+            visited.push(value);
+            value.parse::<u8>().unwrap() * 10
+        });
+
+        assert_eq!(
+            mapped,
+            Tree::Section(vec![
+                Tree::Item(10),
+                Tree::Section(vec![Tree::Item(20), Tree::Section(vec![])]),
+                Tree::Item(30),
+            ])
+        );
+        assert_eq!(visited, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_map_deep() {
+        let tree = (0..1_000).fold(Tree::Item(0), |tree, _| Tree::Section(vec![tree]));
+
+        let mut mapped = tree.map(|value| value + 1);
+        for _ in 0..1_000 {
+            let Tree::Section(mut children) = mapped else {
+                panic!("expected a section");
+            };
+            mapped = children.remove(0);
+        }
+
+        assert_eq!(mapped, Tree::Item(1));
+    }
+
+    #[test]
+    fn test_cut_siblings() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("b"),
+            Tree::Item("c"),
+            Tree::Item("d"),
+            Tree::Item("e"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+        assert_eq!(location.clone().cut_siblings(0, Direction::Left), None);
+
+        let location = location.get_nth(1).unwrap();
+
+        let (cut, right_cut) = location.clone().cut_siblings(2, Direction::Right).unwrap();
+        assert_eq!(cut, vec![Tree::Item("c"), Tree::Item("d")]);
+        assert_eq!(right_cut.cursor, Tree::Item("b"));
+        assert_eq!(
+            right_cut.go_to_root(),
+            Tree::Section(vec![Tree::Item("a"), Tree::Item("b"), Tree::Item("e")])
+        );
+
+        let (cut, left_cut) = location.clone().cut_siblings(1, Direction::Left).unwrap();
+        assert_eq!(cut, vec![Tree::Item("a")]);
+        assert_eq!(left_cut.index(), Some(0));
+
+        assert_eq!(location.clone().cut_siblings(4, Direction::Right), None);
+        assert_eq!(location.cut_siblings(2, Direction::Left), None);
+    }
+
+    #[test]
+    fn test_size_and_height() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Section(vec![])]),
+        ]);
+
+        assert_eq!(tree.size(), 5);
+        assert_eq!(tree.height(), 3);
+        assert_eq!(tree.height(), tree.depth_histogram().len());
+        assert_eq!(Tree::Item("a").size(), 1);
+        assert_eq!(Tree::Item("a").height(), 1);
+        assert_eq!(Tree::<&str>::Section(vec![]).height(), 1);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        }
+        .navigate("dr")
+        .unwrap();
+
+        assert_eq!(location.size(), 3);
+        assert_eq!(location.height(), 2);
+    }
+
+    #[test]
+    fn test_trie_from() {
+        let tree = Tree::trie_from(vec![
+            (vec!["src", "lib.rs"], 1),
+            (vec!["src", "meta.rs"], 2),
+            (vec!["Cargo.toml"], 3),
+            (vec!["src"], 4),
+            (vec!["src", "edit", "log.rs"], 5),
+        ]);
+
+        assert_eq!(
+            tree,
+            Tree::Section(vec![
+                Tree::Section(vec![
+                    Tree::Item(4),
+                    Tree::Item(1),
+                    Tree::Item(2),
+                    Tree::Section(vec![Tree::Item(5)]),
+                ]),
+                Tree::Item(3),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_trie_from_duplicates() {
+        let tree = Tree::trie_from(vec![
+            (vec!['a', 'b'], "first"),
+            (vec![], "root"),
+            (vec!['a', 'b'], "second"),
+        ]);
+
+        assert_eq!(
+            tree,
+            Tree::Section(vec![
+                Tree::Item("root"),
+                Tree::Section(vec![Tree::Item("second")]),
+            ])
+        );
+        assert_eq!(Tree::<u8>::trie_from::<char>(vec![]), Tree::Section(vec![]));
+        assert_eq!(
+            Tree::trie_from(vec![(vec!["c"], 3)]),
+            Tree::Section(vec![Tree::Item(3)])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a".to_string()),
+            Tree::Section(vec![
+                Tree::Item("b".to_string()),
+                Tree::Item("c".to_string()),
+            ]),
+        ]);
+
+        let location = Location::new(tree).navigate("drdr").unwrap();
+        assert_eq!(location.cursor, Tree::Item("c".to_string()));
+
+        let json = serde_json::to_string(&location).unwrap();
+        let restored: Location<String> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, location);
+        assert_eq!(restored.go_to_root(), location.go_to_root());
+    }
+
+    #[test]
+    fn test_to_tree_prealloc() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c"), Tree::Item("d")]),
+            Tree::Item("e"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        }
+        .navigate("drdr")
+        .unwrap();
+
+        assert_eq!(location.clone().to_tree_prealloc(), tree);
+        assert_eq!(
+            location.clone().go_up().unwrap().to_tree_prealloc(),
+            location.go_to_root()
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let tree = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Section(vec![Tree::Item(4)])]),
+            Tree::Item(6),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        };
+
+        let found = location.clone().find(|value| value % 2 == 0).unwrap();
+        assert_eq!(found, location.clone().navigate("drd").unwrap());
+
+        let found = location.clone().find(|value| *value > 2).unwrap();
+        assert_eq!(found.cursor, Tree::Item(4));
+        assert_eq!(found.go_to_root(), tree);
+
+        assert_eq!(location.clone().find(|value| *value > 6), Err(location));
+    }
+
+    #[test]
+    fn test_replace() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        }
+        .get_nth(1)
+        .unwrap();
+
+        let (replaced, old) = location.clone().replace(Tree::Item("-"));
+        assert_eq!(old, Tree::Item("+"));
+        assert_eq!(replaced, location.clone().change(Tree::Item("-")));
+
+        let (restored, _) = replaced.replace(old);
+        assert_eq!(restored, location);
+    }
+
+    #[test]
+    fn test_zip_mut() {
+        let mut tree = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Item(3)]),
+        ]);
+
+        let other = Tree::Section(vec![
+            Tree::Item("x"),
+            Tree::Section(vec![Tree::Item("yy"), Tree::Item("zzz")]),
+        ]);
+
+        assert_eq!(
+            tree.zip_mut(&other, |value, other| *value *= other.len()),
+            Ok(())
+        );
+        assert_eq!(
+            tree,
+            Tree::Section(vec![
+                Tree::Item(1),
+                Tree::Section(vec![Tree::Item(4), Tree::Item(9)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_zip_mut_shape_mismatch() {
+        let mut tree = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Item(3)]),
+        ]);
+        let original = tree.clone();
+
+        let other = Tree::Section(vec![Tree::Item(10), Tree::Section(vec![Tree::Item(20)])]);
+
+        let result = tree.zip_mut(&other, |value, other| *value += other);
+        assert_eq!(result, Err(ShapeMismatch));
+        assert_eq!(tree, original);
+        assert_eq!(ShapeMismatch.to_string(), "the trees have different shapes");
+    }
+
+    #[test]
+    fn test_fork() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        }
+        .navigate("drd")
+        .unwrap();
+
+        let fork = location.fork();
+        assert_eq!(fork, location);
+        assert!(Rc::ptr_eq(&fork.path, &location.path));
+
+        let edited = fork
+            .change(Tree::Item("x"))
+            .insert_right(Tree::Item("y"))
+            .and_then(Location::go_up)
+            .and_then(Location::delete)
+            .unwrap();
+
+        assert_eq!(edited.go_to_root(), Tree::Section(vec![Tree::Item("a")]));
+        assert_eq!(location.cursor, Tree::Item("b"));
+        assert_eq!(location.go_to_root(), tree);
+    }
+
+    #[test]
+    fn test_modify() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![Tree::Item("b")])]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        }
+        .navigate("dr")
+        .unwrap();
+        let path = location.path.clone();
+
+        let location = location.modify(|tree| {
+            if let Tree::Section(children) = tree {
+                children.push(Tree::Item("c"));
+            }
+        });
+
+        assert!(Rc::ptr_eq(&location.path, &path));
+        assert_eq!(
+            location.go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_find_empty_sections() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![]),
+            Tree::Item("a"),
+            Tree::Section(vec![
+                Tree::Item("b"),
+                Tree::Section(vec![Tree::Section(vec![])]),
+                Tree::Section(vec![]),
+            ]),
+        ]);
+
+        assert_eq!(
+            tree.find_empty_sections(),
+            vec![vec![0], vec![2, 1, 0], vec![2, 2]]
+        );
+        assert_eq!(
+            Tree::<&str>::Section(vec![]).find_empty_sections(),
+            vec![Vec::<usize>::new()]
+        );
+        assert!(Tree::Item("a").find_empty_sections().is_empty());
+    }
+
+    #[test]
+    fn test_breadth_first() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("e")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        };
+
+        let items = location
+            .breadth_first()
+            .map(|tree| match tree {
+                Tree::Item(value) => value,
+                Tree::Section(_) => "section",
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            items,
+            vec!["section", "section", "a", "section", "c", "d", "e"]
+        );
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+        assert_eq!(
+            location.breadth_first().nth(3),
+            Some(Tree::Section(vec![Tree::Item("e")]))
+        );
+    }
+
+    #[test]
+    fn test_nav() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("+"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        let found = location
+            .clone()
+            .nav()
+            .down()
+            .right()
+            .right()
+            .nth(1)
+            .left()
+            .finish();
+        assert_eq!(found, location.clone().navigate("d2rd"));
+
+        let found = location.clone().nav().nth(2).down().up().left().finish();
+        assert_eq!(found, location.clone().get_nth(1));
+    }
+
+    #[test]
+    fn test_nav_fails_partway() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert_eq!(location.clone().nav().down().down().right().finish(), None);
+        assert_eq!(location.nav().up().down().finish(), None);
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let inner = Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]);
+        let tree = Tree::Section(vec![Tree::Item("a"), inner.clone()]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree.clone(),
+        };
+        assert_eq!(location.ancestors().next(), None);
+
+        let location = location.navigate("drdr").unwrap();
+        assert_eq!(location.ancestors().collect::<Vec<_>>(), vec![inner, tree]);
+        assert_eq!(location.cursor, Tree::Item("c"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_pretty_json() {
+        let tree = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2)]),
+            Tree::Section(vec![]),
+        ]);
+
+        let json = tree.to_pretty_json();
+        assert_eq!(
+            json,
+            r#"{
+  "Section": [
+    {
+      "Item": 1
+    },
+    {
+      "Section": [
+        {
+          "Item": 2
+        }
+      ]
+    },
+    {
+      "Section": []
+    }
+  ]
+}"#
+        );
+        assert_eq!(serde_json::from_str::<Tree<u8>>(&json).unwrap(), tree);
+    }
+
+    #[test]
+    fn test_delete_returning() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+        assert_eq!(location.clone().delete_returning(), None);
+
+        let section = location.get_nth(1).unwrap();
+        let (deleted, cut) = section.clone().delete_returning().unwrap();
+        assert_eq!(cut, Tree::Section(vec![Tree::Item("b")]));
+        assert_eq!(Some(deleted.clone()), section.delete());
+
+        let pasted = deleted.insert_left(cut).unwrap();
+        assert_eq!(
+            pasted.go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b")]),
+                Tree::Item("c"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rooted() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+
+        let location = Location::rooted(tree.clone());
+        assert_eq!(location.path.as_ref(), &Path::Top);
+        assert_eq!(location.clone().go_up(), None);
+        assert_eq!(location.depth(), 0);
 
-    use crate::{Location, Path, Tree};
+        let first = location.go_down().unwrap();
+        assert_eq!(first.cursor, Tree::Item("a"));
+        assert_eq!(first.go_up().unwrap().cursor, tree);
+    }
 
     #[test]
-    fn test_new() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_delete_only_child_of_nested_section() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
 
-        let location = Location::new(tree.clone());
+        let location = Location::rooted(tree)
+            .navigate("drd")
+            .unwrap()
+            .delete()
+            .unwrap();
 
+        assert_eq!(location.cursor, Tree::Section(vec![]));
         assert_eq!(
-            location,
-            Location {
-                cursor: tree.clone(),
-                path: Path::Node {
-                    left: vec![],
-                    right: vec![tree],
-                    path: Rc::new(Path::Top),
-                }
-                .into(),
+            location.path.as_ref(),
+            &Path::Node {
+                left: vec![Tree::Item("a")],
+                right: vec![Tree::Item("c")],
+                path: Path::Top.into(),
             }
         );
+        assert_eq!(
+            location.go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![]),
+                Tree::Item("c"),
+            ])
+        );
     }
 
     #[test]
-    fn test_for_readme() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_delete_only_child_deeply_nested() {
+        let tree = Tree::Section(vec![Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+        ])]);
 
-        let location = Location::new(tree);
+        let location = Location::rooted(tree)
+            .navigate("ddrd")
+            .unwrap()
+            .delete()
+            .unwrap();
 
-        let location = location.go_down().unwrap();
-        assert_eq!(location.cursor, Tree::Item("a"));
+        assert_eq!(location.cursor, Tree::Section(vec![]));
+        assert_eq!(location.index(), Some(1));
+        assert_eq!(location.depth(), 2);
+        assert_eq!(
+            location.go_to_root(),
+            Tree::Section(vec![Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![]),
+            ])])
+        );
+    }
 
-        let location = location.go_right().unwrap();
-        assert_eq!(location.cursor, Tree::Item("+"));
+    #[test]
+    fn test_swap_right_and_left() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b"), Tree::Item("c")]);
 
-        let location = location.go_left().unwrap();
-        assert_eq!(location.cursor, Tree::Item("a"));
+        let location = Location::rooted(tree.clone());
+        assert_eq!(location.clone().swap_right(), None);
+        assert_eq!(location.clone().swap_left(), None);
 
-        let location = location.insert_right(Tree::Item(".")).unwrap();
+        let first = location.go_down().unwrap();
+        assert_eq!(first.clone().swap_left(), None);
+
+        let moved = first.swap_right().unwrap();
+        assert_eq!(moved.cursor, Tree::Item("a"));
+        assert_eq!(moved.index(), Some(1));
         assert_eq!(
-            location,
-            Location {
-                cursor: Tree::Item("a"),
-                path: Path::Node {
-                    left: vec![],
-                    right: vec![Tree::Item("."), Tree::Item("+"), Tree::Item("b")],
-                    path: Path::Node {
-                        left: vec![],
-                        right: vec![Tree::Section(vec![
-                            Tree::Item("a"),
-                            Tree::Item("+"),
-                            Tree::Item("b")
-                        ])],
-                        path: Path::Top.into()
-                    }
-                    .into()
-                }
-                .into()
-            }
-            .into()
+            moved.clone().go_to_root(),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("a"), Tree::Item("c")])
+        );
+
+        let moved = moved.swap_right().unwrap();
+        assert_eq!(moved.clone().swap_right(), None);
+        assert_eq!(
+            moved.clone().go_to_root(),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c"), Tree::Item("a")])
         );
+
+        let back = moved.swap_left().and_then(Location::swap_left).unwrap();
+        assert_eq!(back.index(), Some(0));
+        assert_eq!(back.go_to_root(), tree);
     }
 
     #[test]
-    fn test_go_left_none() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_siblings() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b"), Tree::Item("c")]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let location = Location::rooted(tree);
+        assert_eq!(location.sibling_count(), None);
+        assert_eq!(location.siblings(), None);
 
-        assert_eq!(location.clone().go_left(), None);
+        let location = location.get_nth(1).unwrap();
+        assert_eq!(location.sibling_count(), Some(3));
+        assert_eq!(
+            location.siblings(),
+            Some(vec![&Tree::Item("a"), &Tree::Item("b"), &Tree::Item("c")])
+        );
     }
 
     #[test]
-    fn test_go_left() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_constructors() {
+        let tree = Tree::section([
+            Tree::item("a"),
+            Tree::section(["b", "c"].map(Tree::item)),
+            Tree::section([]),
+        ]);
 
-        let result = Location {
-            path: Path::Node {
-                left: vec![Tree::Item("a")],
-                right: vec![Tree::Item("b")],
-                path: Path::Node {
-                    left: vec![],
-                    right: vec![tree.clone()],
-                    path: Path::Top.into(),
-                }
-                .into(),
-            }
-            .into(),
-            cursor: Tree::Item("+"),
-        }
-        .go_left();
+        assert_eq!(
+            tree,
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+                Tree::Section(vec![]),
+            ])
+        );
+        assert_eq!(
+            Tree::from(vec![Tree::item(1), Tree::item(2)]),
+            Tree::Section(vec![Tree::Item(1), Tree::Item(2)])
+        );
+    }
 
-        let expect = Some(Location {
-            path: Path::Node {
-                left: vec![],
-                right: vec![Tree::Item("+"), Tree::Item("b")],
-                path: Path::Node {
-                    left: vec![],
-                    right: vec![tree],
-                    path: Path::Top.into(),
-                }
-                .into(),
-            }
-            .into(),
-            cursor: Tree::Item("a"),
-        });
+    #[test]
+    fn test_pretty() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Section(vec![])]),
+            Tree::Item("c"),
+        ]);
 
-        assert_eq!(result, expect,);
+        assert_eq!(
+            tree.pretty(4),
+            "[\n    a\n    [\n        b\n        []\n    ]\n    c\n]"
+        );
+        assert_eq!(tree.to_string(), "[\n  a\n  [\n    b\n    []\n  ]\n  c\n]");
+        assert_eq!(Tree::Item(1).pretty(2), "1");
     }
 
     #[test]
-    fn test_go_right() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_children() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
 
-        let result = Location {
-            path: Path::Node {
-                left: vec![Tree::Item("a")],
-                right: vec![Tree::Item("b")],
-                path: Path::Node {
-                    left: vec![],
-                    right: vec![tree.clone()],
-                    path: Path::Top.into(),
-                }
-                .into(),
-            }
-            .into(),
-            cursor: Tree::Item("+"),
+        let location = Location::rooted(tree);
+        let children = location.clone().children();
+
+        assert_eq!(children.len(), 3);
+        for (index, child) in children.into_iter().enumerate() {
+            assert_eq!(Some(child.clone()), location.clone().get_nth(index));
+            assert_eq!(child.go_up(), Some(location.clone()));
         }
-        .go_right();
 
-        let expect = Some(Location {
-            path: Path::Node {
-                right: vec![],
-                left: vec![Tree::Item("+"), Tree::Item("a")],
-                path: Path::Node {
-                    left: vec![],
-                    right: vec![tree],
-                    path: Path::Top.into(),
-                }
-                .into(),
-            }
-            .into(),
-            cursor: Tree::Item("b"),
-        });
+        assert!(location.clone().get_nth(0).unwrap().children().is_empty());
+        assert!(
+            Location::rooted(Tree::<&str>::Section(vec![]))
+                .children()
+                .is_empty()
+        );
+    }
 
-        assert_eq!(result, expect);
+    #[test]
+    fn test_go_next_and_prev() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![])]),
+            Tree::Section(vec![Tree::Section(vec![Tree::Item("b")])]),
+            Tree::Item("c"),
+        ]);
+
+        let root = Location::rooted(tree);
+        let preorder = root.clone().preorder().collect::<Vec<_>>();
+
+        let forward =
+            std::iter::successors(Some(root.clone()), |location| location.clone().go_next())
+                .collect::<Vec<_>>();
+        assert_eq!(forward, preorder);
+
+        let last = forward.last().unwrap().clone();
+        assert_eq!(last.cursor, Tree::Item("c"));
+
+        let mut backward = std::iter::successors(Some(last), |location| location.clone().go_prev())
+            .collect::<Vec<_>>();
+        backward.reverse();
+        assert_eq!(backward, preorder);
+
+        assert_eq!(root.go_prev(), None);
     }
 
     #[test]
-    fn test_go_right_none() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_leaves() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a".to_string()),
+            Tree::Section(vec![
+                Tree::Section(vec![]),
+                Tree::Item("b".to_string()),
+                Tree::Section(vec![Tree::Item("c".to_string())]),
+            ]),
+            Tree::Item("d".to_string()),
+        ]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        assert_eq!(tree.leaves(), vec!["a", "b", "c", "d"]);
+        assert_eq!(tree.into_leaves(), vec!["a", "b", "c", "d"]);
+        assert!(Tree::<u8>::Section(vec![]).into_leaves().is_empty());
+    }
 
-        assert_eq!(location.clone().go_right(), None);
+    #[test]
+    fn test_ordering() {
+        assert!(Tree::Item(1) < Tree::Item(2));
+        assert!(Tree::Item(9) < Tree::Section(vec![]));
+        assert!(Tree::Section(vec![]) < Tree::Section(vec![Tree::Item(0)]));
+        assert!(
+            Tree::Section(vec![Tree::Item(1), Tree::Item(5)]) < Tree::Section(vec![Tree::Item(2)])
+        );
+        assert!(
+            Tree::Section(vec![Tree::Item(1), Tree::Item(9)])
+                < Tree::Section(vec![Tree::Item(1), Tree::Section(vec![])])
+        );
+        assert!(
+            Tree::Section(vec![Tree::Section(vec![Tree::Item(1)])])
+                < Tree::Section(vec![Tree::Section(vec![Tree::Item(1), Tree::Item(0)])])
+        );
+
+        let set = [
+            Tree::Section(vec![Tree::Item(1)]),
+            Tree::Item(3),
+            Tree::Section(vec![]),
+            Tree::Item(3),
+            Tree::Item(1),
+        ]
+        .into_iter()
+        .collect::<std::collections::BTreeSet<_>>();
+
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            vec![
+                Tree::Item(1),
+                Tree::Item(3),
+                Tree::Section(vec![]),
+                Tree::Section(vec![Tree::Item(1)]),
+            ]
+        );
     }
 
     #[test]
-    fn test_go_up_none() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_fold() {
+        let tree = Tree::Section(vec![
+            Tree::Item("1"),
+            Tree::Section(vec![Tree::Item("2"), Tree::Item("3")]),
+        ]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let location = Location::rooted(tree.clone());
+        assert_eq!(
+            location.fold(String::new(), |acc, value| acc + value),
+            "123"
+        );
+        assert_eq!(
+            location
+                .get_nth(1)
+                .unwrap()
+                .fold(0, |acc, value| acc + value.parse::<u32>().unwrap()),
+            5
+        );
 
-        assert_eq!(location.clone().go_up(), None);
+        let product = tree.fold(
+            |value| value.parse::<u32>().unwrap(),
+            |results| results.into_iter().product(),
+        );
+        assert_eq!(product, 6);
+
+        let depth = tree.fold(|_| 0, |results| 1 + results.into_iter().max().unwrap_or(0));
+        assert_eq!(depth, 2);
     }
 
     #[test]
-    fn test_go_up() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_insert_many() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("z")]);
 
-        let location = Location {
-            path: Path::Node {
+        let location = Location::rooted(tree);
+        assert_eq!(location.clone().insert_right_many([Tree::Item("b")]), None);
+        assert_eq!(location.clone().insert_left_many([Tree::Item("b")]), None);
+
+        let location = location
+            .go_down()
+            .unwrap()
+            .insert_right_many(["b", "c", "d"].map(Tree::Item))
+            .unwrap();
+
+        assert_eq!(location.cursor, Tree::Item("a"));
+        assert_eq!(
+            location.path.as_ref(),
+            &Path::Node {
                 left: vec![],
-                right: vec![Tree::Item("+"), Tree::Item("b")],
+                right: ["b", "c", "d", "z"].map(Tree::Item).to_vec(),
                 path: Path::Top.into(),
             }
-            .into(),
-            cursor: Tree::Item("a"),
-        }
-        .go_up();
+        );
 
+        let location = location
+            .go_rightmost()
+            .unwrap()
+            .insert_left_many(["w", "x", "y"].map(Tree::Item))
+            .unwrap();
+
+        assert_eq!(location.cursor, Tree::Item("z"));
+        assert_eq!(location.index(), Some(7));
         assert_eq!(
-            location,
-            Some(Location {
-                cursor: tree.clone(),
-                path: Path::Top.into(),
-            })
+            location.go_to_root(),
+            Tree::Section(
+                ["a", "b", "c", "d", "w", "x", "y", "z"]
+                    .map(Tree::Item)
+                    .to_vec()
+            )
         );
     }
 
     #[test]
-    fn test_go_down_none() {
-        let tree = Tree::Item("a");
+    fn test_path_indices() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Section(vec![Tree::Item("c")])]),
+        ]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let root = Location::rooted(tree);
+        assert!(root.path_indices().is_empty());
 
-        assert_eq!(location.go_down(), None);
+        let location = root.clone().navigate("drdrd").unwrap();
+        assert_eq!(location.cursor, Tree::Item("c"));
+        assert_eq!(location.path_indices(), vec![1, 1, 0]);
+
+        let bookmark = location.path_indices();
+        let restored = location
+            .clone()
+            .root_location()
+            .navigate_indices(&bookmark)
+            .unwrap();
+        assert_eq!(restored, location);
+
+        assert_eq!(
+            root.clone().get_nth(1).unwrap().navigate_indices(&[1, 0]),
+            Some(location)
+        );
+        assert_eq!(root.clone().navigate_indices(&[]), Some(root.clone()));
+        assert_eq!(root.navigate_indices(&[0, 0]), None);
     }
 
     #[test]
-    fn test_go_down() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_up_n_and_down_first_n() {
+        let tree = Tree::Section(vec![
+            Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]),
+            Tree::Item("c"),
+        ]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let root = Location::rooted(tree);
+        assert_eq!(root.clone().up_n(0), Some(root.clone()));
+        assert_eq!(root.clone().down_first_n(0), Some(root.clone()));
 
-        assert_eq!(
-            location.go_down(),
-            Some(Location {
-                cursor: Tree::Item("a"),
-                path: Path::Node {
-                    left: [].into(),
-                    right: [Tree::Item("+"), Tree::Item("b")].into(),
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
-        );
+        let leaf = root.clone().down_first_n(2).unwrap();
+        assert_eq!(leaf.cursor, Tree::Item("a"));
+        assert_eq!(root.clone().down_first_n(3), None);
+
+        assert_eq!(leaf.clone().up_n(2), Some(root));
+        assert_eq!(leaf.up_n(3), None);
     }
 
     #[test]
-    fn test_get_nth_0() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_predicates() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![])]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let root = Location::rooted(tree);
+        assert!(root.is_top());
+        assert!(root.is_section());
+        assert!(!root.is_empty_section());
+        assert!(!root.is_leaf());
 
-        assert_eq!(
-            location.get_nth(0),
-            Some(Location {
-                cursor: Tree::Item("a"),
-                path: Path::Node {
-                    left: [].into(),
-                    right: [Tree::Item("+"), Tree::Item("b")].into(),
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
-        );
+        let item = root.clone().go_down().unwrap();
+        assert!(!item.is_top());
+        assert!(item.is_leaf());
+        assert!(!item.is_section());
+        assert!(!item.is_empty_section());
+
+        let empty = item.go_right().unwrap();
+        assert!(empty.is_section());
+        assert!(empty.is_empty_section());
+        assert!(!empty.is_leaf());
+
+        assert!(!Location::new(Tree::Item("a")).is_top());
     }
 
     #[test]
-    fn test_get_nth_1() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_fold_up() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Section(vec![Tree::Item("c")])]),
+        ]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let location = Location::rooted(tree.clone()).navigate("drdrd").unwrap();
+
+        let (root, sizes) = location.clone().fold_up(vec![], |mut sizes, ancestor| {
+            sizes.push(ancestor.size());
+            sizes
+        });
+
+        assert_eq!(root, tree);
+        assert_eq!(sizes, vec![2, 4, 6]);
+
+        let (root, visited) = Location::rooted(tree.clone()).fold_up(0, |count, _| count + 1);
+        assert_eq!(root, tree);
+        assert_eq!(visited, 0);
+    }
+
+    #[test]
+    fn test_replace_children() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+        let location = Location::rooted(tree).get_nth(1).unwrap();
+        let path = location.path.clone();
 
+        let location = location.replace_children(vec![Tree::Item("x")]).unwrap();
+        assert_eq!(location.cursor, Tree::Section(vec![Tree::Item("x")]));
+        assert_eq!(location.path, path);
         assert_eq!(
-            location.get_nth(1),
-            Some(Location {
-                cursor: Tree::Item("+"),
-                path: Path::Node {
-                    left: [Tree::Item("a")].into(),
-                    right: [Tree::Item("b")].into(),
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
+            location.clone().go_to_root(),
+            Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![Tree::Item("x")])])
         );
+
+        let leaf = location.go_down().unwrap();
+        assert!(leaf.replace_children(vec![]).is_none());
     }
 
     #[test]
-    fn test_get_nth_2() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_tree_get() {
+        let mut tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        assert_eq!(tree.get(&[]), Some(&tree));
+        assert_eq!(tree.get(&[1, 1]), Some(&Tree::Item("c")));
+        assert_eq!(tree.get(&[2]), None);
+        assert_eq!(tree.get(&[0, 0]), None);
 
+        *tree.get_mut(&[1, 0]).unwrap() = Tree::Item("x");
         assert_eq!(
-            location.get_nth(2),
-            Some(Location {
-                cursor: Tree::Item("b"),
-                path: Path::Node {
-                    left: [Tree::Item("+"), Tree::Item("a")].into(),
-                    right: [].into(),
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
+            tree,
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("x"), Tree::Item("c")]),
+            ])
         );
+        assert!(tree.get_mut(&[1, 2]).is_none());
     }
 
     #[test]
-    fn test_get_nth_out_of_bounds() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_from_iterator() {
+        let tree: Tree<i32> = (1..=3).collect();
+        assert_eq!(
+            tree,
+            Tree::Section(vec![Tree::Item(1), Tree::Item(2), Tree::Item(3)])
+        );
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let tree: Tree<&str> = vec!["a", "b"].into_iter().collect();
+        assert_eq!(tree, Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]));
 
-        assert_eq!(location.get_nth(3), None);
+        let tree: Tree<u8> = std::iter::empty().collect();
+        assert_eq!(tree, Tree::Section(vec![]));
     }
 
     #[test]
-    fn test_change() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_nth_or_insert_with() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+        let location = Location::rooted(tree);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let existing = location
+            .clone()
+            .nth_or_insert_with(1, || unreachable!())
+            .unwrap();
+        assert_eq!(existing, location.clone().get_nth(1).unwrap());
 
-        let new_tree = Tree::Item("z");
+        let appended = location.nth_or_insert_with(5, || Tree::Item("c")).unwrap();
+        assert_eq!(appended.cursor, Tree::Item("c"));
+        assert_eq!(appended.index(), Some(2));
+        assert_eq!(
+            appended.clone().go_to_root(),
+            Tree::Section(vec![Tree::Item("a"), Tree::Item("b"), Tree::Item("c")])
+        );
 
+        let empty = Location::rooted(Tree::Section(vec![]));
         assert_eq!(
-            location.change(new_tree.clone()),
-            Location {
-                cursor: new_tree,
-                path: Path::Top.into(),
-            }
+            empty
+                .nth_or_insert_with(0, || Tree::Item("x"))
+                .unwrap()
+                .cursor,
+            Tree::Item("x")
         );
+        assert!(appended.nth_or_insert_with(0, || Tree::Item("x")).is_none());
     }
 
     #[test]
-    fn test_change_after_go_left() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
-
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+    fn test_go_up_with_shared_path() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+        let location = Location::rooted(tree.clone()).down_first_n(1).unwrap();
+        let location = location.go_right().unwrap().go_down().unwrap();
+        let fork = location.fork();
 
-        let new_tree = Tree::Item("-");
+        assert_eq!(location.go_to_root(), tree);
+        assert_eq!(fork.cursor, Tree::Item("b"));
+        assert_eq!(fork.path_indices(), vec![1, 0]);
+        assert_eq!(fork.go_to_root(), tree);
+    }
 
-        let updated_location = location
-            .go_down()
-            .and_then(Location::go_right)
-            .map(|loc| loc.change(new_tree.clone()));
+    #[test]
+    fn test_context() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("b"),
+            Tree::Section(vec![Tree::Item("c"), Tree::Item("d"), Tree::Item("e")]),
+            Tree::Item("f"),
+        ]);
+        let location = Location::rooted(tree).navigate_indices(&[2, 1]).unwrap();
 
         assert_eq!(
-            updated_location,
-            Some(Location {
-                cursor: Tree::Item("-"),
-                path: Path::Node {
-                    left: [Tree::Item("a")].into(),
-                    right: [Tree::Item("b")].into(),
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
+            location.context(),
+            vec![
+                (&[Tree::Item("c")][..], &[Tree::Item("e")][..]),
+                (
+                    &[Tree::Item("b"), Tree::Item("a")][..],
+                    &[Tree::Item("f")][..]
+                ),
+            ]
         );
+        assert!(location.root_location().context().is_empty());
     }
 
     #[test]
-    fn test_insert_left() {
-        let result = Location {
-            path: Path::Node {
-                left: vec![],
-                right: vec![Tree::Item("+"), Tree::Item("b")],
-                path: Path::Top.into(),
-            }
-            .into(),
-            cursor: Tree::Item("a"),
-        }
-        .insert_left(Tree::Item("."));
+    fn test_zip_with() {
+        let before = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Item(3)]),
+            Tree::Section(vec![]),
+        ]);
+        let after = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(5), Tree::Item(1)]),
+            Tree::Section(vec![]),
+        ]);
 
-        let expect = Location {
-            path: Path::Node {
-                left: vec![Tree::Item(".")],
-                right: vec![Tree::Item("+"), Tree::Item("b")],
-                path: Path::Top.into(),
-            }
-            .into(),
-            cursor: Tree::Item("a"),
-        }
-        .into();
+        assert_eq!(
+            before.zip_with(&after, |old, new| new - old),
+            Some(Tree::Section(vec![
+                Tree::Item(0),
+                Tree::Section(vec![Tree::Item(3), Tree::Item(-2)]),
+                Tree::Section(vec![]),
+            ]))
+        );
 
-        assert_eq!(result, expect);
+        let item_vs_section =
+            Tree::Section(vec![Tree::Item(1), Tree::Item(2), Tree::Section(vec![])]);
+        assert_eq!(before.zip_with(&item_vs_section, |a, b| a + b), None);
+
+        let shorter = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2)]),
+            Tree::Section(vec![]),
+        ]);
+        assert_eq!(before.zip_with(&shorter, |a, b| a + b), None);
     }
 
     #[test]
-    fn test_insert_left_none() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_cut_and_paste() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("b"),
+            Tree::Item("c"),
+            Tree::Item("d"),
+        ]);
+        let location = Location::rooted(tree).get_nth(1).unwrap();
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let (location, clipboard) = location.cut().unwrap();
+        assert_eq!(clipboard, Tree::Item("b"));
+        assert_eq!(location.cursor, Tree::Item("c"));
 
-        let new_tree = Tree::Item("-");
+        let location = location.go_right().unwrap().paste_right(clipboard).unwrap();
+        assert_eq!(location.cursor, Tree::Item("b"));
+        assert_eq!(location.index(), Some(3));
+        assert_eq!(
+            location.clone().go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Item("c"),
+                Tree::Item("d"),
+                Tree::Item("b"),
+            ])
+        );
 
-        assert!(location.insert_left(new_tree).is_none());
+        let location = location
+            .go_leftmost()
+            .unwrap()
+            .paste_left(Tree::Item("y"))
+            .unwrap()
+            .paste_left(Tree::Item("x"))
+            .unwrap();
+        assert_eq!(location.index(), Some(0));
+        assert_eq!(
+            location.clone().go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("x"),
+                Tree::Item("y"),
+                Tree::Item("a"),
+                Tree::Item("c"),
+                Tree::Item("d"),
+                Tree::Item("b"),
+            ])
+        );
+
+        let top = location.root_location();
+        assert!(top.clone().cut().is_none());
+        assert!(top.clone().paste_right(Tree::Item("z")).is_none());
+        assert!(top.paste_left(Tree::Item("z")).is_none());
     }
 
     #[test]
-    fn test_insert_right_none() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
-
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+    fn test_into_item_and_into_section() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Section(vec![Tree::Item("b")])]);
+        let location = Location::rooted(tree);
 
-        let new_tree = Tree::Item("-");
+        let item = location.clone().go_down().unwrap();
+        assert_eq!(item.as_children(), None);
+        assert_eq!(item.clone().into_item(), Ok("a"));
+        assert_eq!(item.clone().into_section(), Err(item));
 
-        assert!(location.insert_right(new_tree).is_none());
+        let section = location.get_nth(1).unwrap();
+        assert_eq!(section.as_children(), Some(&[Tree::Item("b")][..]));
+        assert_eq!(section.clone().into_section(), Ok(vec![Tree::Item("b")]));
+        assert_eq!(section.clone().into_item(), Err(section));
     }
 
     #[test]
-    fn test_insert_right() {
-        let result = Location {
-            path: Path::Node {
-                left: vec![],
-                right: vec![Tree::Item("+"), Tree::Item("b")],
-                path: Path::Top.into(),
-            }
-            .into(),
-            cursor: Tree::Item("a"),
-        }
-        .insert_right(Tree::Item("."));
-
-        let expect = Location {
-            path: Path::Node {
-                left: vec![],
-                right: vec![Tree::Item("."), Tree::Item("+"), Tree::Item("b")],
-                path: Path::Top.into(),
-            }
-            .into(),
-            cursor: Tree::Item("a"),
-        }
-        .into();
+    fn test_default() {
+        assert_eq!(Tree::<u8>::default(), Tree::Section(vec![]));
+        assert_eq!(
+            Location::<u8>::default(),
+            Location::new(Tree::Section(vec![]))
+        );
 
-        assert_eq!(result, expect);
+        let mut tree = Tree::Section(vec![Tree::Item(1)]);
+        let taken = std::mem::take(&mut tree);
+        assert_eq!(taken, Tree::Section(vec![Tree::Item(1)]));
+        assert_eq!(tree, Tree::Section(vec![]));
     }
 
     #[test]
-    fn test_insert_down() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
-
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
-
-        let new_tree = Tree::Item("-");
-        let updated_location = location.insert_down(new_tree);
+    fn test_location_map() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c"), Tree::Item("d")]),
+        ]);
+        let location = Location::rooted(tree.clone())
+            .navigate_indices(&[1, 1])
+            .unwrap();
 
+        let mapped: Location<String> = location.clone().map(|value| value.to_uppercase());
+        assert_eq!(mapped.cursor, Tree::Item("C".to_string()));
+        assert_eq!(mapped.path_indices(), location.path_indices());
         assert_eq!(
-            updated_location,
-            Some(Location {
-                cursor: Tree::Item("-"),
-                path: Path::Node {
-                    left: [].into(),
-                    right: vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")],
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
+            mapped.context(),
+            vec![
+                (
+                    &[Tree::Item("B".to_string())][..],
+                    &[Tree::Item("D".to_string())][..]
+                ),
+                (&[Tree::Item("A".to_string())][..], &[][..]),
+            ]
         );
+        assert_eq!(mapped.go_to_root(), tree.map(str::to_uppercase));
     }
 
     #[test]
-    fn test_insert_down_none() {
-        let location = Location {
-            path: Path::Node {
-                left: vec![Tree::Item("a")],
-                right: vec![Tree::Item("b")],
-                path: Path::Top.into(),
-            }
-            .into(),
-            cursor: Tree::Item("+"),
-        };
+    fn test_same_tree() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+        let root = Location::rooted(tree);
+        let nested = root.clone().navigate_indices(&[1, 1]).unwrap();
 
-        let new_tree = Tree::Item("-");
-        let updated_location = location.insert_down(new_tree);
+        assert!(root.same_tree(&nested));
+        assert!(nested.same_tree(&root.clone().go_down().unwrap()));
+        assert_eq!(nested.cursor, Tree::Item("c"));
 
-        assert_eq!(updated_location, None);
+        let appended = root
+            .clone()
+            .go_down()
+            .unwrap()
+            .insert_right(Tree::Item("x"))
+            .and_then(Location::go_right)
+            .unwrap()
+            .delete()
+            .unwrap();
+        let swapped_back = root
+            .clone()
+            .go_down()
+            .unwrap()
+            .swap_right()
+            .unwrap()
+            .swap_left()
+            .unwrap();
+        assert!(appended.same_tree(&swapped_back));
+
+        let changed = nested.clone().change(Tree::Item("z"));
+        assert!(!changed.same_tree(&root));
     }
 
     #[test]
-    fn test_delete_top() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+    fn test_splice() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![
+                Tree::Item("b"),
+                Tree::Section(vec![]),
+                Tree::Item("c"),
+            ]),
+            Tree::Item("d"),
+        ]);
+        let location = Location::rooted(tree).get_nth(1).unwrap();
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
+        let location = location.splice().unwrap();
+        assert_eq!(location.cursor, Tree::Item("b"));
+        assert_eq!(location.index(), Some(1));
+        assert_eq!(
+            location.clone().go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Item("b"),
+                Tree::Section(vec![]),
+                Tree::Item("c"),
+                Tree::Item("d"),
+            ])
+        );
 
-        assert_eq!(location.delete(), None);
+        assert!(location.clone().splice().is_none());
+        assert!(location.clone().go_right().unwrap().splice().is_none());
+        assert!(location.root_location().splice().is_none());
     }
 
     #[test]
-    fn test_delete_middle_node() {
-        let location = Location {
-            path: Path::Node {
-                left: vec![Tree::Item("+"), Tree::Item("a")],
-                right: vec![],
-                path: Path::Top.into(),
-            }
-            .into(),
-            cursor: Tree::Item("b"),
-        };
+    fn test_wrap_right() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("b"),
+            Tree::Item("c"),
+            Tree::Item("d"),
+        ]);
+        let location = Location::rooted(tree.clone()).get_nth(1).unwrap();
 
-        let updated_location = location.delete();
+        let wrapped = location.clone().wrap_right(2).unwrap();
+        assert_eq!(
+            wrapped.cursor,
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c"), Tree::Item("d")])
+        );
+        assert_eq!(wrapped.index(), Some(1));
+        assert_eq!(
+            wrapped.clone().go_to_root(),
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("b"), Tree::Item("c"), Tree::Item("d")]),
+            ])
+        );
+        assert_eq!(wrapped.splice().unwrap(), location);
 
         assert_eq!(
-            updated_location,
-            Some(Location {
-                cursor: Tree::Item("+"),
-                path: Path::Node {
-                    left: [Tree::Item("a")].into(),
-                    right: [].into(),
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
+            location.clone().wrap_right(0).unwrap().cursor,
+            Tree::Section(vec![Tree::Item("b")])
         );
+        assert!(location.clone().wrap_right(3).is_none());
+        assert!(location.root_location().wrap_right(0).is_none());
     }
 
     #[test]
-    fn test_delete_last_node() {
-        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
-
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
-
-        let updated_location = location.go_down().and_then(Location::delete);
+    fn test_retain_children() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("+"),
+            Tree::Section(vec![Tree::Item("+")]),
+            Tree::Item("+"),
+            Tree::Item("b"),
+        ]);
+        let location = Location::rooted(tree);
+        let path = location.path.clone();
 
+        let location = location
+            .retain_children(|child| child != &Tree::Item("+"))
+            .unwrap();
+        assert_eq!(location.path, path);
         assert_eq!(
-            updated_location,
-            Some(Location {
-                cursor: Tree::Item("+"),
-                path: Path::Node {
-                    right: [Tree::Item("b")].into(),
-                    left: [].into(),
-                    path: crate::Path::Top.into(),
-                }
-                .into()
-            })
+            location.cursor,
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Section(vec![Tree::Item("+")]),
+                Tree::Item("b"),
+            ])
         );
+
+        let leaf = location.go_down().unwrap();
+        assert!(leaf.retain_children(|_| true).is_none());
     }
 
     #[test]
-    fn test_delete_only_child() {
-        let tree = Tree::Section(vec![Tree::Item("a")]);
+    fn test_visit_mut() {
+        let mut tree = Tree::Section(vec![
+            Tree::Item(" a ".to_string()),
+            Tree::Section(vec![Tree::Item("b".to_string())]),
+        ]);
 
-        let location = Location {
-            path: Path::Top.into(),
-            cursor: tree,
-        };
-
-        let updated_location = location.go_down().and_then(Location::delete);
+        let mut visited = 0;
+        tree.visit_mut(|node| {
+            visited += 1;
+            if let Tree::Item(value) = node {
+                *value = value.trim().to_uppercase();
+            }
+        });
+        assert_eq!(visited, 4);
+        assert_eq!(
+            tree,
+            Tree::Section(vec![
+                Tree::Item("A".to_string()),
+                Tree::Section(vec![Tree::Item("B".to_string())]),
+            ])
+        );
 
+        let mut visited = vec![];
+        tree.visit_mut(|node| match node {
+            Tree::Item(value) => visited.push(value.clone()),
+            Tree::Section(children) => children.push(Tree::Section(vec![])),
+        });
+        assert_eq!(visited, vec!["A".to_string(), "B".to_string()]);
         assert_eq!(
-            updated_location,
-            Some(Location {
-                cursor: Tree::Section(vec![]),
-                path: crate::Path::Top.into(),
-            })
+            tree,
+            Tree::Section(vec![
+                Tree::Item("A".to_string()),
+                Tree::Section(vec![Tree::Item("B".to_string()), Tree::Section(vec![])]),
+                Tree::Section(vec![]),
+            ])
         );
     }
 }
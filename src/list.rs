@@ -0,0 +1,193 @@
+//! The list zipper: a cursor over a flat, non-empty list.
+//!
+//! [`ListLocation`] is the one-dimensional counterpart of [`crate::Location`].
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a location (cursor) within a non-empty list, the classic list zipper.
+///
+/// Both sides keep the elements nearest to the focus at the end of their
+/// vector, so moving the focus only pushes and pops: `left` is in list
+/// order and `right` is in reverse list order.
+pub struct ListLocation<T: Clone> {
+    /// Elements to the left of the focus, nearest last.
+    pub left: Vec<T>,
+    /// The element being focused on.
+    pub focus: T,
+    /// Elements to the right of the focus, nearest last.
+    pub right: Vec<T>,
+}
+
+impl<T: Clone> ListLocation<T> {
+    /// Creates a location focused on the first element of a list.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The elements of the list, in order.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ListLocation)` - If the list is not empty.
+    /// * `None` - If the list is empty.
+    pub fn from_vec(mut values: Vec<T>) -> Option<Self> {
+        values.reverse();
+        let focus = values.pop()?;
+
+        Some(Self {
+            left: vec![],
+            focus,
+            right: values,
+        })
+    }
+
+    /// Converts the location back into a list.
+    ///
+    /// # Returns
+    ///
+    /// The elements of the list, in order.
+    pub fn to_vec(self) -> Vec<T> {
+        let mut values = self.left;
+        values.push(self.focus);
+        values.extend(self.right.into_iter().rev());
+        values
+    }
+
+    /// Moves the focus to the previous element.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ListLocation)` - If there is a previous element.
+    /// * `None` - If the focus is on the first element.
+    pub fn move_left(mut self) -> Option<Self> {
+        let focus = self.left.pop()?;
+        self.right.push(self.focus);
+
+        Some(Self {
+            left: self.left,
+            focus,
+            right: self.right,
+        })
+    }
+
+    /// Moves the focus to the next element.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ListLocation)` - If there is a next element.
+    /// * `None` - If the focus is on the last element.
+    pub fn move_right(mut self) -> Option<Self> {
+        let focus = self.right.pop()?;
+        self.left.push(self.focus);
+
+        Some(Self {
+            left: self.left,
+            focus,
+            right: self.right,
+        })
+    }
+
+    /// Inserts a new element after the focus and moves the focus onto it.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The element to insert.
+    ///
+    /// # Returns
+    ///
+    /// A new location focused on the inserted element.
+    pub fn insert(mut self, value: T) -> Self {
+        self.left.push(self.focus);
+
+        Self {
+            left: self.left,
+            focus: value,
+            right: self.right,
+        }
+    }
+
+    /// Deletes the focused element and moves the focus to the next element,
+    /// or to the previous one if there is no next element.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ListLocation)` - If the list had another element.
+    /// * `None` - If the focused element was the only one.
+    pub fn delete(mut self) -> Option<Self> {
+        let focus = self.right.pop().or_else(|| self.left.pop())?;
+
+        Some(Self {
+            left: self.left,
+            focus,
+            right: self.right,
+        })
+    }
+
+    /// Replaces the focused element, handing back the old one.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The new element.
+    ///
+    /// # Returns
+    ///
+    /// A new location focused on the new element, and the replaced element.
+    pub fn replace(self, value: T) -> (Self, T) {
+        (
+            Self {
+                left: self.left,
+                focus: value,
+                right: self.right,
+            },
+            self.focus,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::ListLocation;
+
+    #[test]
+    fn test_from_vec_and_to_vec() {
+        let location = ListLocation::from_vec(vec![1, 2, 3]).unwrap();
+        assert_eq!(location.focus, 1);
+        assert_eq!(location.clone().to_vec(), vec![1, 2, 3]);
+
+        let location = location.move_right().unwrap().move_right().unwrap();
+        assert_eq!(location.focus, 3);
+        assert_eq!(location.to_vec(), vec![1, 2, 3]);
+
+        assert_eq!(ListLocation::<u8>::from_vec(vec![]), None);
+    }
+
+    #[test]
+    fn test_move() {
+        let location = ListLocation::from_vec(vec!["a", "b"]).unwrap();
+        assert_eq!(location.clone().move_left(), None);
+
+        let location = location.move_right().unwrap();
+        assert_eq!(location.focus, "b");
+        assert_eq!(location.clone().move_right(), None);
+        assert_eq!(location.move_left().unwrap().focus, "a");
+    }
+
+    #[test]
+    fn test_edit() {
+        let location = ListLocation::from_vec(vec!["a", "c"]).unwrap();
+
+        let location = location.insert("b");
+        assert_eq!(location.focus, "b");
+
+        let (location, old) = location.replace("B");
+        assert_eq!(old, "b");
+        assert_eq!(location.clone().to_vec(), vec!["a", "B", "c"]);
+
+        let location = location.delete().unwrap();
+        assert_eq!(location.focus, "c");
+
+        let location = location.delete().unwrap();
+        assert_eq!(location.focus, "a");
+        assert_eq!(location.clone().to_vec(), vec!["a"]);
+        assert_eq!(location.delete(), None);
+    }
+}
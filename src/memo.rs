@@ -0,0 +1,266 @@
+//! A location that memoizes child lookups, see [`MemoLocation`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::{Location, Path, Tree};
+
+#[derive(Debug, Clone)]
+/// A location that caches the locations of its children.
+///
+/// Looking up the nth child with [`Location::get_nth`] walks the children
+/// every time; `MemoLocation::get_nth` only walks once per index and hands
+/// out shared locations afterwards. Indices without a child are cached as
+/// well, so repeated out-of-bounds lookups are answered straight away.
+/// Edits made through `change_nth` and `insert_nth` keep the cache
+/// consistent, so a stale child is never served.
+pub struct MemoLocation<T: Clone> {
+    location: Location<T>,
+    cache: RefCell<HashMap<usize, Option<Rc<Location<T>>>>>,
+}
+
+impl<T: Clone> MemoLocation<T> {
+    /// Wraps a location with an empty cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - The location whose children are looked up.
+    ///
+    /// # Returns
+    ///
+    /// A new `MemoLocation`.
+    pub fn new(location: Location<T>) -> Self {
+        Self {
+            location,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the wrapped location.
+    pub fn location(&self) -> &Location<T> {
+        &self.location
+    }
+
+    /// Unwraps the location, dropping the cache.
+    pub fn into_inner(self) -> Location<T> {
+        self.location
+    }
+
+    /// Gets the nth child of the current node, see [`Location::get_nth`].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the child to navigate to.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Rc<Location>)` - The location of the child, from the cache if it was looked up before.
+    /// * `None` - If the child doesn't exist or the current node is an item.
+    pub fn get_nth(&self, n: usize) -> Option<Rc<Location<T>>> {
+        if let Some(child) = self.cache.borrow().get(&n) {
+            return child.clone();
+        }
+
+        let child = self.location.clone().get_nth(n).map(Rc::new);
+        self.cache.borrow_mut().insert(n, child.clone());
+        child
+    }
+
+    /// Replaces the nth child of the current node.
+    ///
+    /// Every child location holds its siblings, so the change makes all
+    /// cached children stale: every key is invalidated except `n`, which is
+    /// refreshed with the location of the new child, and the indices cached
+    /// as absent, which stay absent.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the child to replace.
+    /// * `tree` - The new child.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(MemoLocation)` - With the child replaced.
+    /// * `None` - If the child doesn't exist or the current node is an item.
+    pub fn change_nth(self, n: usize, tree: Tree<T>) -> Option<Self> {
+        let Tree::Section(children) = &self.location.cursor else {
+            return None;
+        };
+        if n >= children.len() {
+            return None;
+        }
+
+        let child = Location {
+            cursor: tree,
+            path: Path::Node {
+                left: children[..n].iter().rev().cloned().collect(),
+                right: children[n + 1..].to_vec(),
+                path: self.location.path.clone(),
+            }
+            .into(),
+        };
+
+        Some(self.refresh(n, child))
+    }
+
+    /// Inserts a new child so that it becomes the nth child of the current node.
+    ///
+    /// The children from `n` on move one position to the right. Every child
+    /// location holds its siblings, so all cached children are invalidated
+    /// except `n`, which is refreshed with the location of the new child.
+    /// Of the indices cached as absent, the one taken by the last child is
+    /// invalidated and the others stay absent.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the new child, at most the number of children.
+    /// * `tree` - The new child.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(MemoLocation)` - With the child inserted.
+    /// * `None` - If `n` is past the end or the current node is an item.
+    pub fn insert_nth(self, n: usize, tree: Tree<T>) -> Option<Self> {
+        let Tree::Section(children) = &self.location.cursor else {
+            return None;
+        };
+        if n > children.len() {
+            return None;
+        }
+
+        let child = Location {
+            cursor: tree,
+            path: Path::Node {
+                left: children[..n].iter().rev().cloned().collect(),
+                right: children[n..].to_vec(),
+                path: self.location.path.clone(),
+            }
+            .into(),
+        };
+
+        Some(self.refresh(n, child))
+    }
+
+    fn refresh(self, n: usize, child: Location<T>) -> Self {
+        let location = child
+            .clone()
+            .go_up()
+            .expect("a child location has a parent");
+
+        let len = match &location.cursor {
+            Tree::Section(children) => children.len(),
+            Tree::Item(_) => 0,
+        };
+
+        let mut cache = self.cache.into_inner();
+        cache.retain(|&index, child| child.is_none() && index >= len);
+        cache.insert(n, Some(Rc::new(child)));
+
+        Self {
+            location,
+            cache: RefCell::new(cache),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::rc::Rc;
+
+    use crate::{Location, MemoLocation, Tree};
+
+    #[test]
+    fn test_get_nth() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+        let location = Location::rooted(tree);
+
+        let memo = MemoLocation::new(location.clone());
+        let first = memo.get_nth(1).unwrap();
+        let second = memo.get_nth(1).unwrap();
+
+        assert!(Rc::ptr_eq(&first, &second));
+        assert_eq!(*first, location.clone().get_nth(1).unwrap());
+        assert_eq!(memo.get_nth(2), None);
+        assert_eq!(memo.into_inner(), location);
+    }
+
+    #[test]
+    fn test_change_nth() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b"), Tree::Item("c")]);
+
+        let memo = MemoLocation::new(Location::rooted(tree));
+        let stale_changed = memo.get_nth(1).unwrap();
+        let stale_sibling = memo.get_nth(0).unwrap();
+
+        let memo = memo.change_nth(1, Tree::Item("x")).unwrap();
+        let expected = Location::rooted(Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("x"),
+            Tree::Item("c"),
+        ]));
+
+        assert_eq!(memo.location(), &expected);
+        assert_ne!(memo.get_nth(1).unwrap(), stale_changed);
+        assert_eq!(
+            *memo.get_nth(1).unwrap(),
+            expected.clone().get_nth(1).unwrap()
+        );
+        assert_ne!(memo.get_nth(0).unwrap(), stale_sibling);
+        assert_eq!(*memo.get_nth(0).unwrap(), expected.get_nth(0).unwrap());
+
+        assert!(memo.clone().change_nth(3, Tree::Item("y")).is_none());
+    }
+
+    #[test]
+    fn test_get_nth_out_of_bounds_is_cached() {
+        let tree = Tree::Section(vec![Tree::Item("a")]);
+
+        let memo = MemoLocation::new(Location::rooted(tree));
+        assert_eq!(memo.get_nth(5), None);
+        assert_eq!(memo.cache.borrow().get(&5), Some(&None));
+        assert_eq!(memo.get_nth(5), None);
+
+        let memo = memo.change_nth(0, Tree::Item("b")).unwrap();
+        assert_eq!(memo.cache.borrow().get(&5), Some(&None));
+
+        let memo = MemoLocation::new(Location::rooted(Tree::Item("a")));
+        assert_eq!(memo.get_nth(0), None);
+        assert_eq!(memo.cache.borrow().get(&0), Some(&None));
+    }
+
+    #[test]
+    fn test_insert_nth() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+
+        let memo = MemoLocation::new(Location::rooted(tree));
+        let _ = memo.get_nth(1).unwrap();
+
+        let memo = memo.insert_nth(1, Tree::Item("+")).unwrap();
+        assert_eq!(memo.get_nth(1).unwrap().cursor, Tree::Item("+"));
+        assert_eq!(memo.get_nth(2).unwrap().cursor, Tree::Item("b"));
+
+        let memo = memo.insert_nth(3, Tree::Item("c")).unwrap();
+        assert_eq!(
+            memo.location().cursor,
+            Tree::Section(vec![
+                Tree::Item("a"),
+                Tree::Item("+"),
+                Tree::Item("b"),
+                Tree::Item("c"),
+            ])
+        );
+
+        assert!(memo.clone().insert_nth(5, Tree::Item("d")).is_none());
+        assert_eq!(memo.get_nth(4), None);
+
+        let memo = memo.insert_nth(4, Tree::Item("d")).unwrap();
+        assert_eq!(memo.get_nth(4).unwrap().cursor, Tree::Item("d"));
+        assert!(
+            MemoLocation::new(Location::rooted(Tree::Item("a")))
+                .insert_nth(0, Tree::Item("b"))
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! Trees that carry metadata on every node, and a zipper over them.
+//!
+//! [`MetaTree`] keeps side data such as selection or fold state next to each
+//! node, so the item type does not have to hold it.
+
+use std::rc::Rc;
+
+use crate::Tree;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a hierarchical tree structure where every node carries metadata.
+///
+/// It mirrors [`Tree`], keeping per-node side data such as selection or fold
+/// state next to the nodes instead of inside the item type.
+pub enum MetaTree<T: Clone, M: Clone> {
+    /// A single item value of type T with its metadata.
+    Item(T, M),
+    /// A collection of trees forming a section, with the metadata of the section.
+    Section(Vec<MetaTree<T, M>>, M),
+}
+
+impl<T: Clone> Tree<T> {
+    /// Converts the tree into a `MetaTree`, giving every node the default metadata.
+    ///
+    /// # Returns
+    ///
+    /// A `MetaTree` with the same shape and values as this tree.
+    pub fn attach_meta<M: Default + Clone>(&self) -> MetaTree<T, M> {
+        match self {
+            Tree::Item(value) => MetaTree::Item(value.clone(), M::default()),
+            Tree::Section(children) => MetaTree::Section(
+                children.iter().map(Tree::attach_meta).collect(),
+                M::default(),
+            ),
+        }
+    }
+}
+
+impl<T: Clone, M: Clone> MetaTree<T, M> {
+    /// Returns the metadata of the node.
+    pub fn meta(&self) -> &M {
+        match self {
+            MetaTree::Item(_, meta) | MetaTree::Section(_, meta) => meta,
+        }
+    }
+
+    /// Returns a mutable reference to the metadata of the node.
+    pub fn meta_mut(&mut self) -> &mut M {
+        match self {
+            MetaTree::Item(_, meta) | MetaTree::Section(_, meta) => meta,
+        }
+    }
+
+    /// Converts the tree back into a plain `Tree`, dropping all metadata.
+    ///
+    /// # Returns
+    ///
+    /// A `Tree` with the same shape and values as this tree.
+    pub fn to_tree(&self) -> Tree<T> {
+        match self {
+            MetaTree::Item(value, _) => Tree::Item(value.clone()),
+            MetaTree::Section(children, _) => {
+                Tree::Section(children.iter().map(MetaTree::to_tree).collect())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a path within a `MetaTree`, mirroring [`Path`](crate::Path).
+///
+/// Besides the siblings, every node of the path keeps the metadata of the
+/// parent section so that it can be restored when moving up.
+pub enum MetaPath<T: Clone, M: Clone> {
+    /// Represents the top level of the tree hierarchy.
+    Top,
+    /// Represents a position within the tree structure.
+    Node {
+        /// Trees to the left of the current position.
+        left: Vec<MetaTree<T, M>>,
+        /// Trees to the right of the current position.
+        right: Vec<MetaTree<T, M>>,
+        /// Metadata of the parent section.
+        meta: M,
+        /// Path to the parent node.
+        path: Rc<MetaPath<T, M>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a location (cursor) within a `MetaTree`, mirroring [`Location`](crate::Location).
+pub struct MetaLocation<T: Clone, M: Clone> {
+    /// The current tree node being focused on.
+    pub cursor: MetaTree<T, M>,
+    /// The path representing the context of this location within the overall tree.
+    pub path: Rc<MetaPath<T, M>>,
+}
+
+impl<T: Clone, M: Clone> MetaLocation<T, M> {
+    /// Creates a new location focused on the root of a `MetaTree`.
+    ///
+    /// Unlike [`Location::new`](crate::Location::new), the location starts at
+    /// `MetaPath::Top`, so `go_up` returns `None` straight away.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to create a location from.
+    ///
+    /// # Returns
+    ///
+    /// A new `MetaLocation` instance with the given tree as cursor.
+    pub fn new(tree: MetaTree<T, M>) -> Self {
+        Self {
+            cursor: tree,
+            path: MetaPath::Top.into(),
+        }
+    }
+
+    /// Moves the cursor to the left sibling.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(MetaLocation)` - If there is a left sibling.
+    /// * `None` - If there is no left sibling or the location is at the top.
+    pub fn go_left(self) -> Option<Self> {
+        match self.path.as_ref() {
+            MetaPath::Top => None,
+            MetaPath::Node {
+                left,
+                right,
+                meta,
+                path,
+            } => left.split_first().map(|(first, rest)| Self {
+                cursor: first.clone(),
+                path: MetaPath::Node {
+                    left: rest.to_vec(),
+                    right: vec![self.cursor].into_iter().chain(right.clone()).collect(),
+                    meta: meta.clone(),
+                    path: path.clone(),
+                }
+                .into(),
+            }),
+        }
+    }
+
+    /// Moves the cursor to the right sibling.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(MetaLocation)` - If there is a right sibling.
+    /// * `None` - If there is no right sibling or the location is at the top.
+    pub fn go_right(self) -> Option<Self> {
+        match self.path.as_ref() {
+            MetaPath::Top => None,
+            MetaPath::Node {
+                left,
+                right,
+                meta,
+                path,
+            } => right.split_first().map(|(first, rest)| Self {
+                cursor: first.clone(),
+                path: MetaPath::Node {
+                    left: vec![self.cursor].into_iter().chain(left.clone()).collect(),
+                    right: rest.to_vec(),
+                    meta: meta.clone(),
+                    path: path.clone(),
+                }
+                .into(),
+            }),
+        }
+    }
+
+    /// Moves the cursor to the parent node, restoring its metadata.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(MetaLocation)` - If there is a parent node.
+    /// * `None` - If the location is at the top.
+    pub fn go_up(self) -> Option<Self> {
+        match self.path.as_ref() {
+            MetaPath::Top => None,
+            MetaPath::Node {
+                left,
+                right,
+                meta,
+                path,
+            } => Self {
+                cursor: MetaTree::Section(
+                    left.iter()
+                        .rev()
+                        .cloned()
+                        .chain([self.cursor])
+                        .chain(right.iter().cloned())
+                        .collect(),
+                    meta.clone(),
+                ),
+                path: path.clone(),
+            }
+            .into(),
+        }
+    }
+
+    /// Moves the cursor to the first child node.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(MetaLocation)` - If the current node is a section with at least one child.
+    /// * `None` - If the current node is an item or an empty section.
+    pub fn go_down(self) -> Option<Self> {
+        match self.cursor {
+            MetaTree::Item(..) => None,
+            MetaTree::Section(children, meta) => children.split_first().map(|(first, rest)| Self {
+                cursor: first.clone(),
+                path: MetaPath::Node {
+                    left: vec![],
+                    right: rest.to_vec(),
+                    meta,
+                    path: self.path,
+                }
+                .into(),
+            }),
+        }
+    }
+
+    /// Returns the metadata of the current node.
+    pub fn meta(&self) -> &M {
+        self.cursor.meta()
+    }
+
+    /// Replaces the metadata of the current node.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The new metadata.
+    ///
+    /// # Returns
+    ///
+    /// A new location with the updated cursor.
+    pub fn set_meta(mut self, meta: M) -> Self {
+        *self.cursor.meta_mut() = meta;
+        self
+    }
+
+    /// Replaces the current node with a new tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The new tree to replace the current node with.
+    ///
+    /// # Returns
+    ///
+    /// A new location with the updated cursor.
+    pub fn change(self, tree: MetaTree<T, M>) -> Self {
+        Self {
+            cursor: tree,
+            path: self.path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{MetaLocation, MetaPath, MetaTree, Tree};
+
+    #[test]
+    fn test_attach_meta() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("+"), Tree::Item("b")]);
+
+        let meta_tree = tree.attach_meta::<bool>();
+
+        assert_eq!(
+            meta_tree,
+            MetaTree::Section(
+                vec![
+                    MetaTree::Item("a", false),
+                    MetaTree::Item("+", false),
+                    MetaTree::Item("b", false),
+                ],
+                false
+            )
+        );
+        assert_eq!(meta_tree.to_tree(), tree);
+    }
+
+    #[test]
+    fn test_navigation() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = MetaLocation::new(tree.attach_meta::<u8>());
+        assert_eq!(location.clone().go_up(), None);
+
+        let location = location.go_down().unwrap().go_right().unwrap();
+        assert_eq!(
+            location.cursor,
+            MetaTree::Section(vec![MetaTree::Item("b", 0), MetaTree::Item("c", 0)], 0)
+        );
+
+        let location = location.go_down().unwrap().go_right().unwrap();
+        assert_eq!(location.cursor, MetaTree::Item("c", 0));
+        assert_eq!(location.clone().go_right(), None);
+
+        let location = location.go_left().unwrap();
+        assert_eq!(location.cursor, MetaTree::Item("b", 0));
+        assert_eq!(location.go_left(), None);
+    }
+
+    #[test]
+    fn test_set_meta() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = MetaLocation::new(tree.attach_meta::<bool>())
+            .go_down()
+            .and_then(MetaLocation::go_right)
+            .map(|location| location.set_meta(true))
+            .and_then(MetaLocation::go_down)
+            .and_then(MetaLocation::go_right)
+            .map(|location| location.set_meta(true))
+            .unwrap();
+
+        assert!(location.meta());
+        assert_eq!(
+            location.path.as_ref(),
+            &MetaPath::Node {
+                left: vec![MetaTree::Item("b", false)],
+                right: vec![],
+                meta: true,
+                path: MetaPath::Node {
+                    left: vec![MetaTree::Item("a", false)],
+                    right: vec![],
+                    meta: false,
+                    path: MetaPath::Top.into(),
+                }
+                .into(),
+            }
+        );
+
+        let root = location.go_up().and_then(MetaLocation::go_up).unwrap();
+        assert_eq!(
+            root.cursor,
+            MetaTree::Section(
+                vec![
+                    MetaTree::Item("a", false),
+                    MetaTree::Section(
+                        vec![MetaTree::Item("b", false), MetaTree::Item("c", true)],
+                        true
+                    ),
+                ],
+                false
+            )
+        );
+        assert_eq!(root.cursor.to_tree(), tree);
+    }
+
+    #[test]
+    fn test_change() {
+        let location = MetaLocation::new(Tree::Section(vec![Tree::Item("a")]).attach_meta::<u8>())
+            .go_down()
+            .unwrap()
+            .change(MetaTree::Item("z", 7))
+            .go_up()
+            .unwrap();
+
+        assert_eq!(
+            location.cursor,
+            MetaTree::Section(vec![MetaTree::Item("z", 7)], 0)
+        );
+        assert_eq!(location.go_down().unwrap().meta(), &7);
+    }
+}
@@ -0,0 +1,146 @@
+//! Structural patterns for matching subtrees, see [`Pattern`].
+
+use crate::{Location, Tree};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents the shape of a subtree, used to test trees structurally.
+pub enum Pattern<T: Clone> {
+    /// Matches any single node.
+    Any,
+    /// Matches an item equal to the given value.
+    Item(T),
+    /// Matches a section whose children match the given patterns, in order.
+    Section(Vec<Pattern<T>>),
+    /// Inside a section pattern, matches zero or more children.
+    ///
+    /// Used on its own it behaves like `Pattern::Any`.
+    Rest,
+}
+
+impl<T: Clone + PartialEq> Pattern<T> {
+    /// Tests whether a tree matches the pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to test.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the tree has the shape described by the pattern.
+    pub fn matches(&self, tree: &Tree<T>) -> bool {
+        match (self, tree) {
+            (Pattern::Any | Pattern::Rest, _) => true,
+            (Pattern::Item(expected), Tree::Item(value)) => expected == value,
+            (Pattern::Section(patterns), Tree::Section(children)) => {
+                Self::matches_children(patterns, children)
+            }
+            _ => false,
+        }
+    }
+
+    fn matches_children(patterns: &[Pattern<T>], children: &[Tree<T>]) -> bool {
+        match patterns.split_first() {
+            None => children.is_empty(),
+            Some((Pattern::Rest, patterns)) => (0..=children.len())
+                .any(|skipped| Self::matches_children(patterns, &children[skipped..])),
+            Some((pattern, patterns)) => children.split_first().is_some_and(|(child, children)| {
+                pattern.matches(child) && Self::matches_children(patterns, children)
+            }),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Location<T> {
+    /// Tests whether the subtree under the cursor matches a pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to test against.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the current node has the shape described by the pattern.
+    pub fn matches(&self, pattern: &Pattern<T>) -> bool {
+        pattern.matches(&self.cursor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{Location, Path, Pattern, Tree};
+
+    #[test]
+    fn test_matches() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("+"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let location = Location {
+            path: Path::Top.into(),
+            cursor: tree,
+        };
+
+        assert!(location.matches(&Pattern::Any));
+        assert!(location.matches(&Pattern::Section(vec![
+            Pattern::Item("a"),
+            Pattern::Item("+"),
+            Pattern::Section(vec![Pattern::Any, Pattern::Any]),
+        ])));
+        assert!(!location.matches(&Pattern::Section(vec![
+            Pattern::Item("a"),
+            Pattern::Item("-"),
+            Pattern::Any,
+        ])));
+        assert!(!location.matches(&Pattern::Section(vec![Pattern::Any, Pattern::Any])));
+        assert!(!location.matches(&Pattern::Item("a")));
+
+        let location = location.go_down().unwrap();
+        assert!(location.matches(&Pattern::Item("a")));
+        assert!(!location.matches(&Pattern::Section(vec![])));
+    }
+
+    #[test]
+    fn test_matches_rest() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("+"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let patterns = [
+            (Pattern::Section(vec![Pattern::Rest]), true),
+            (
+                Pattern::Section(vec![Pattern::Item("a"), Pattern::Rest]),
+                true,
+            ),
+            (
+                Pattern::Section(vec![Pattern::Rest, Pattern::Item("+"), Pattern::Rest]),
+                true,
+            ),
+            (
+                Pattern::Section(vec![
+                    Pattern::Rest,
+                    Pattern::Section(vec![Pattern::Rest, Pattern::Item("c")]),
+                ]),
+                true,
+            ),
+            (
+                Pattern::Section(vec![Pattern::Item("a"), Pattern::Rest, Pattern::Item("a")]),
+                false,
+            ),
+            (
+                Pattern::Section(vec![Pattern::Rest, Pattern::Item("b")]),
+                false,
+            ),
+        ];
+
+        for (pattern, expected) in patterns {
+            assert_eq!(pattern.matches(&tree), expected, "{pattern:?}");
+        }
+
+        assert!(Pattern::Section(vec![Pattern::Rest]).matches(&Tree::<&str>::Section(vec![])));
+    }
+}
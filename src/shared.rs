@@ -0,0 +1,146 @@
+//! An immutable tree shared through `Arc`, built with [`crate::Tree::into_shared`].
+
+use std::sync::Arc;
+
+use crate::Tree;
+
+#[derive(Debug, PartialEq)]
+enum SharedNode<T: Clone> {
+    Item(T),
+    Section(Vec<SharedTree<T>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents an immutable tree whose nodes are shared through `Arc`.
+///
+/// It is the shared-ownership counterpart of [`Tree`]: cloning a
+/// `SharedTree`, or any subtree reached through it, only clones an `Arc`
+/// handle, so big trees can be cached and handed out cheaply.
+pub struct SharedTree<T: Clone>(Arc<SharedNode<T>>);
+
+impl<T: Clone> Tree<T> {
+    /// Converts the tree into a `SharedTree`.
+    ///
+    /// # Returns
+    ///
+    /// A `SharedTree` with the same shape and values as this tree.
+    pub fn into_shared(self) -> SharedTree<T> {
+        SharedTree(Arc::new(match self {
+            Tree::Item(value) => SharedNode::Item(value),
+            Tree::Section(children) => {
+                SharedNode::Section(children.into_iter().map(Tree::into_shared).collect())
+            }
+        }))
+    }
+}
+
+impl<T: Clone> SharedTree<T> {
+    /// Returns the value of the node if it is an item.
+    pub fn as_item(&self) -> Option<&T> {
+        match self.0.as_ref() {
+            SharedNode::Item(value) => Some(value),
+            SharedNode::Section(_) => None,
+        }
+    }
+
+    /// Returns the children of the node if it is a section.
+    pub fn children(&self) -> Option<&[SharedTree<T>]> {
+        match self.0.as_ref() {
+            SharedNode::Item(_) => None,
+            SharedNode::Section(children) => Some(children),
+        }
+    }
+
+    /// Returns a handle to the child at the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The index of the child.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(SharedTree)` - If the node is a section with a child at index `n`.
+    /// * `None` - If the node is an item or the index is out of bounds.
+    pub fn child(&self, n: usize) -> Option<SharedTree<T>> {
+        self.children()?.get(n).cloned()
+    }
+
+    /// Returns a handle to the node at the given index path.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - Child indices leading from this node to the target node.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(SharedTree)` - If the index path exists.
+    /// * `None` - If any of the indices does not exist.
+    pub fn get(&self, indices: &[usize]) -> Option<SharedTree<T>> {
+        indices
+            .iter()
+            .try_fold(self.clone(), |node, &index| node.child(index))
+    }
+
+    /// Returns true if both handles point to the same shared node.
+    pub fn ptr_eq(&self, other: &SharedTree<T>) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Copies the shared tree back into an owning `Tree`.
+    ///
+    /// # Returns
+    ///
+    /// A `Tree` with the same shape and values as this tree.
+    pub fn to_tree(&self) -> Tree<T> {
+        match self.0.as_ref() {
+            SharedNode::Item(value) => Tree::Item(value.clone()),
+            SharedNode::Section(children) => {
+                Tree::Section(children.iter().map(SharedTree::to_tree).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::Tree;
+
+    #[test]
+    fn test_into_shared() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let shared = tree.clone().into_shared();
+        let copy = shared.clone();
+
+        assert!(copy.ptr_eq(&shared));
+        assert_eq!(copy, shared);
+        assert_eq!(copy.to_tree(), tree);
+    }
+
+    #[test]
+    fn test_shared_navigation() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")]),
+        ]);
+
+        let shared = tree.into_shared();
+
+        assert_eq!(shared.as_item(), None);
+        assert_eq!(shared.children().map(<[_]>::len), Some(2));
+        assert_eq!(shared.get(&[1, 1]).unwrap().as_item(), Some(&"c"));
+        assert_eq!(shared.get(&[0, 0]), None);
+        assert_eq!(shared.child(2), None);
+
+        let section = shared.child(1).unwrap();
+        assert!(section.ptr_eq(&shared.get(&[1]).unwrap()));
+        assert_eq!(
+            section.to_tree(),
+            Tree::Section(vec![Tree::Item("b"), Tree::Item("c")])
+        );
+    }
+}
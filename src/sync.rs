@@ -0,0 +1,254 @@
+//! A thread-safe zipper whose path is shared through `Arc` instead of `Rc`.
+//!
+//! [`SyncLocation`] can be sent across threads and converts to and from
+//! [`crate::Location`].
+
+use std::sync::Arc;
+
+use crate::{Location, Path, Tree};
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a path within a tree like [`Path`], shared through `Arc` instead of `Rc`.
+pub enum SyncPath<T: Clone> {
+    /// Represents the top level of the tree hierarchy.
+    Top,
+    /// Represents a position within the tree structure.
+    Node {
+        /// Trees to the left of the current position, nearest first.
+        left: Vec<Tree<T>>,
+        /// Trees to the right of the current position.
+        right: Vec<Tree<T>>,
+        /// Path to the parent node.
+        path: Arc<SyncPath<T>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a location (cursor) within a tree that can be sent across threads.
+///
+/// It mirrors [`Location`], but its path is shared through `Arc`, so a
+/// `SyncLocation<T>` is `Send + Sync` whenever `T` is. Converting from and
+/// into a `Location` copies the path.
+pub struct SyncLocation<T: Clone> {
+    /// The current tree node being focused on.
+    pub cursor: Tree<T>,
+    /// The path representing the context of this location within the overall tree.
+    pub path: Arc<SyncPath<T>>,
+}
+
+impl<T: Clone> SyncLocation<T> {
+    /// Creates a location focused on a tree as the root, see [`Location::rooted`].
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to create a location from.
+    ///
+    /// # Returns
+    ///
+    /// A new `SyncLocation` at `SyncPath::Top` with the given tree as cursor.
+    pub fn rooted(tree: Tree<T>) -> Self {
+        Self {
+            cursor: tree,
+            path: SyncPath::Top.into(),
+        }
+    }
+
+    /// Moves the cursor to the left sibling, see [`Location::go_left`].
+    pub fn go_left(self) -> Option<Self> {
+        let SyncPath::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+        let (first, rest) = left.split_first()?;
+
+        Some(Self {
+            cursor: first.clone(),
+            path: SyncPath::Node {
+                left: rest.to_vec(),
+                right: [self.cursor].into_iter().chain(right.clone()).collect(),
+                path: path.clone(),
+            }
+            .into(),
+        })
+    }
+
+    /// Moves the cursor to the right sibling, see [`Location::go_right`].
+    pub fn go_right(self) -> Option<Self> {
+        let SyncPath::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+        let (first, rest) = right.split_first()?;
+
+        Some(Self {
+            cursor: first.clone(),
+            path: SyncPath::Node {
+                left: [self.cursor].into_iter().chain(left.clone()).collect(),
+                right: rest.to_vec(),
+                path: path.clone(),
+            }
+            .into(),
+        })
+    }
+
+    /// Moves the cursor to the parent node, see [`Location::go_up`].
+    pub fn go_up(self) -> Option<Self> {
+        let SyncPath::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+
+        Some(Self {
+            cursor: Tree::Section(
+                left.iter()
+                    .rev()
+                    .cloned()
+                    .chain([self.cursor])
+                    .chain(right.iter().cloned())
+                    .collect(),
+            ),
+            path: path.clone(),
+        })
+    }
+
+    /// Moves the cursor to the first child node, see [`Location::go_down`].
+    pub fn go_down(self) -> Option<Self> {
+        let Tree::Section(children) = &self.cursor else {
+            return None;
+        };
+        let (first, rest) = children.split_first()?;
+
+        Some(Self {
+            cursor: first.clone(),
+            path: SyncPath::Node {
+                left: vec![],
+                right: rest.to_vec(),
+                path: self.path,
+            }
+            .into(),
+        })
+    }
+
+    /// Replaces the current node, see [`Location::change`].
+    pub fn change(self, tree: Tree<T>) -> Self {
+        Self {
+            cursor: tree,
+            path: self.path,
+        }
+    }
+
+    /// Moves up to the top and returns the whole tree, see [`Location::go_to_root`].
+    pub fn go_to_root(self) -> Tree<T> {
+        let mut location = self;
+        while !matches!(location.path.as_ref(), SyncPath::Top) {
+            location = location.go_up().expect("a node always has a parent");
+        }
+        location.cursor
+    }
+}
+
+impl<T: Clone> From<Location<T>> for SyncLocation<T> {
+    /// Copies the `Rc` path of a location into an `Arc` path.
+    fn from(location: Location<T>) -> Self {
+        let mut path = Arc::new(SyncPath::Top);
+
+        for (left, right) in location.context().into_iter().rev() {
+            path = SyncPath::Node {
+                left: left.to_vec(),
+                right: right.to_vec(),
+                path,
+            }
+            .into();
+        }
+
+        Self {
+            cursor: location.cursor,
+            path,
+        }
+    }
+}
+
+impl<T: Clone> From<SyncLocation<T>> for Location<T> {
+    /// Copies the `Arc` path of a sync location back into an `Rc` path.
+    fn from(location: SyncLocation<T>) -> Self {
+        let mut levels = vec![];
+        let mut sync_path = location.path.as_ref();
+
+        while let SyncPath::Node {
+            left,
+            right,
+            path: parent,
+        } = sync_path
+        {
+            levels.push((left, right));
+            sync_path = parent;
+        }
+
+        let mut path = Path::Top.into();
+        for (left, right) in levels.into_iter().rev() {
+            path = Path::Node {
+                left: left.clone(),
+                right: right.clone(),
+                path,
+            }
+            .into();
+        }
+
+        Self {
+            cursor: location.cursor,
+            path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::thread;
+
+    use crate::{Location, SyncLocation, Tree};
+
+    #[test]
+    fn test_navigate_in_thread() {
+        let tree = Tree::Section(vec![
+            Tree::Item(1),
+            Tree::Section(vec![Tree::Item(2), Tree::Item(3)]),
+        ]);
+        let location = SyncLocation::rooted(tree);
+
+        let handle = thread::spawn(move || {
+            let location = location
+                .go_down()
+                .and_then(SyncLocation::go_right)
+                .and_then(SyncLocation::go_down)
+                .and_then(SyncLocation::go_right)
+                .unwrap();
+            assert_eq!(location.cursor, Tree::Item(3));
+            assert_eq!(location.clone().go_right(), None);
+
+            location.change(Tree::Item(4)).go_to_root()
+        });
+
+        assert_eq!(
+            handle.join().unwrap(),
+            Tree::Section(vec![
+                Tree::Item(1),
+                Tree::Section(vec![Tree::Item(2), Tree::Item(4)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_convert_location() {
+        let tree = Tree::Section(vec![
+            Tree::Item("a"),
+            Tree::Item("b"),
+            Tree::Section(vec![Tree::Item("c"), Tree::Item("d")]),
+        ]);
+        let location = Location::rooted(tree.clone())
+            .navigate_indices(&[2, 1])
+            .unwrap();
+
+        let sync = SyncLocation::from(location.clone());
+        assert_eq!(sync.clone().go_left().unwrap().cursor, Tree::Item("c"));
+        assert_eq!(sync.clone().go_to_root(), tree);
+        assert_eq!(Location::from(sync), location);
+    }
+}
@@ -0,0 +1,243 @@
+//! A zipper over any recursive structure implementing [`Zippable`].
+//!
+//! [`crate::Location`] is tied to [`Tree`]; the types here offer the same
+//! navigation for user-defined trees. `Tree<T>` implements `Zippable`, so it
+//! can be used with both.
+
+use std::rc::Rc;
+
+use crate::Tree;
+
+/// A recursive structure whose nodes may have an ordered list of children.
+///
+/// Branch nodes are rebuilt from their children alone when moving up, so
+/// `from_children(node.children().unwrap().to_vec())` must give back a node
+/// equal to `node`.
+pub trait Zippable: Clone + Sized {
+    /// Returns the children of the node, or `None` if it is a leaf.
+    fn children(&self) -> Option<&[Self]>;
+
+    /// Builds a branch node from its children.
+    fn from_children(children: Vec<Self>) -> Self;
+}
+
+impl<T: Clone> Zippable for Tree<T> {
+    fn children(&self) -> Option<&[Self]> {
+        match self {
+            Tree::Item(_) => None,
+            Tree::Section(children) => Some(children),
+        }
+    }
+
+    fn from_children(children: Vec<Self>) -> Self {
+        Tree::Section(children)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a path within a `Zippable` structure, mirroring [`crate::Path`].
+pub enum Path<Z: Zippable> {
+    /// Represents the top level of the hierarchy.
+    Top,
+    /// Represents a position within the structure.
+    Node {
+        /// Nodes to the left of the current position, nearest first.
+        left: Vec<Z>,
+        /// Nodes to the right of the current position.
+        right: Vec<Z>,
+        /// Path to the parent node.
+        path: Rc<Path<Z>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Represents a location (cursor) within a `Zippable` structure, mirroring [`crate::Location`].
+pub struct Location<Z: Zippable> {
+    /// The current node being focused on.
+    pub cursor: Z,
+    /// The path representing the context of this location within the overall structure.
+    pub path: Rc<Path<Z>>,
+}
+
+impl<Z: Zippable> Location<Z> {
+    /// Creates a new location focused on the root of a structure, at `Path::Top`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The structure to create a location from.
+    ///
+    /// # Returns
+    ///
+    /// A new `Location` instance with the given root as cursor.
+    pub fn new(root: Z) -> Self {
+        Self {
+            cursor: root,
+            path: Path::Top.into(),
+        }
+    }
+
+    /// Moves the cursor to the left sibling.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a left sibling.
+    /// * `None` - If there is no left sibling or the location is at the top.
+    pub fn go_left(self) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+        let (first, rest) = left.split_first()?;
+
+        Some(Self {
+            cursor: first.clone(),
+            path: Path::Node {
+                left: rest.to_vec(),
+                right: [self.cursor].into_iter().chain(right.clone()).collect(),
+                path: path.clone(),
+            }
+            .into(),
+        })
+    }
+
+    /// Moves the cursor to the right sibling.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a right sibling.
+    /// * `None` - If there is no right sibling or the location is at the top.
+    pub fn go_right(self) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+        let (first, rest) = right.split_first()?;
+
+        Some(Self {
+            cursor: first.clone(),
+            path: Path::Node {
+                left: [self.cursor].into_iter().chain(left.clone()).collect(),
+                right: rest.to_vec(),
+                path: path.clone(),
+            }
+            .into(),
+        })
+    }
+
+    /// Moves the cursor to the parent node, rebuilding it with [`Zippable::from_children`].
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If there is a parent node.
+    /// * `None` - If the location is at the top.
+    pub fn go_up(self) -> Option<Self> {
+        let Path::Node { left, right, path } = self.path.as_ref() else {
+            return None;
+        };
+
+        Some(Self {
+            cursor: Z::from_children(
+                left.iter()
+                    .rev()
+                    .cloned()
+                    .chain([self.cursor])
+                    .chain(right.iter().cloned())
+                    .collect(),
+            ),
+            path: path.clone(),
+        })
+    }
+
+    /// Moves the cursor to the first child node.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Location)` - If the current node has at least one child.
+    /// * `None` - If the current node is a leaf or has no children.
+    pub fn go_down(self) -> Option<Self> {
+        let (first, rest) = self.cursor.children()?.split_first()?;
+
+        Some(Self {
+            cursor: first.clone(),
+            path: Path::Node {
+                left: vec![],
+                right: rest.to_vec(),
+                path: self.path,
+            }
+            .into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{Location, Path, Zippable};
+    use crate::Tree;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Json {
+        Number(i64),
+        Array(Vec<Json>),
+    }
+
+    impl Zippable for Json {
+        fn children(&self) -> Option<&[Self]> {
+            match self {
+                Json::Number(_) => None,
+                Json::Array(values) => Some(values),
+            }
+        }
+
+        fn from_children(children: Vec<Self>) -> Self {
+            Json::Array(children)
+        }
+    }
+
+    #[test]
+    fn test_custom_zippable() {
+        let json = Json::Array(vec![
+            Json::Number(1),
+            Json::Array(vec![Json::Number(2), Json::Number(3)]),
+        ]);
+
+        let location = Location::new(json.clone());
+        assert_eq!(location.clone().go_up(), None);
+
+        let location = location
+            .go_down()
+            .and_then(Location::go_right)
+            .and_then(Location::go_down)
+            .and_then(Location::go_right)
+            .unwrap();
+        assert_eq!(location.cursor, Json::Number(3));
+        assert_eq!(location.clone().go_right(), None);
+        assert_eq!(location.clone().go_down(), None);
+
+        let location = Location {
+            cursor: Json::Number(4),
+            path: location.path,
+        };
+        let root = location.go_up().and_then(Location::go_up).unwrap();
+        assert_eq!(
+            root.cursor,
+            Json::Array(vec![
+                Json::Number(1),
+                Json::Array(vec![Json::Number(2), Json::Number(4)]),
+            ])
+        );
+        assert_eq!(root.path.as_ref(), &Path::Top);
+    }
+
+    #[test]
+    fn test_tree_zippable() {
+        let tree = Tree::Section(vec![Tree::Item("a"), Tree::Item("b")]);
+
+        let location = Location::new(tree.clone()).go_down().unwrap();
+        assert_eq!(location.cursor, Tree::Item("a"));
+        assert_eq!(
+            location.clone().go_right().unwrap().go_left(),
+            Some(location.clone())
+        );
+        assert_eq!(location.go_up().unwrap().cursor, tree);
+        assert_eq!(Tree::Item("a").children(), None);
+    }
+}